@@ -1,6 +1,8 @@
-use tiger_parse::{TigerFlags, TigerReadable, TigerReader};
+use tiger_parse::{TigerFlags, TigerReadable};
 
-bitflags::bitflags! {
+tiger_parse::reflection_container!();
+
+tiger_parse::bitflags::bitflags! {
     #[derive(Debug, Clone, TigerFlags)]
     struct TestBitFlags32: u32 {
         const NONE = 0;
@@ -10,6 +12,23 @@ bitflags::bitflags! {
     }
 }
 
+tiger_parse::bitflags::bitflags! {
+    #[derive(Debug, Clone, PartialEq, TigerFlags)]
+    #[tiger(mode = "strict")]
+    struct TestBitFlagsStrict: u8 {
+        const FLAG_A = 0x1;
+        const FLAG_B = 0x2;
+    }
+}
+
+tiger_parse::bitflags::bitflags! {
+    #[derive(Debug, Clone, PartialEq, TigerFlags)]
+    #[tiger(mode = "retain")]
+    struct TestBitFlagsRetain: u16 {
+        const FLAG_A = 0x1;
+    }
+}
+
 fn main() {
     const DATA: &[u8] = &[0x5, 0, 0xFF, 1];
     let mut cursor = std::io::Cursor::new(&DATA);
@@ -17,4 +36,21 @@ fn main() {
     println!("{:?}", flags);
     assert!(flags.contains(TestBitFlags32::FLAG_A));
     assert!(flags.contains(TestBitFlags32::FLAG_C));
+
+    const STRICT_DATA: &[u8] = &[0x3, 0x5];
+    let mut cursor = std::io::Cursor::new(&STRICT_DATA);
+    let flags = TestBitFlagsStrict::read_ds(&mut cursor).unwrap();
+    assert_eq!(flags, TestBitFlagsStrict::all());
+    let e = TestBitFlagsStrict::read_ds(&mut cursor).unwrap_err();
+    println!("Error (this is expected): {e}");
+
+    const RETAIN_DATA: &[u8] = &[0x3, 0x1];
+    let mut cursor = std::io::Cursor::new(&RETAIN_DATA);
+    let flags = TestBitFlagsRetain::read_ds(&mut cursor).unwrap();
+    assert_eq!(flags.bits(), 0x103);
+
+    #[cfg(feature = "reflect")]
+    for flags in FLAGS.iter() {
+        println!("{flags}");
+    }
 }
@@ -0,0 +1,166 @@
+use tiger_parse::{
+    diff::{self, Change},
+    source::{MemoryTagSource, TagEntry},
+    tiger_type, tiger_variant, Endian, Tag, TigerFlags,
+};
+use tiger_pkg::TagHash;
+
+tiger_parse::reflection_container!();
+
+#[derive(Debug, Clone, Copy)]
+#[repr(u8)]
+#[tiger_type]
+pub enum EBlendMode {
+    Opaque = 0,
+    Additive = 1,
+    #[tiger(unknown)]
+    Unknown(u8),
+}
+
+tiger_parse::bitflags::bitflags! {
+    #[derive(Debug, Clone, TigerFlags)]
+    pub struct MaterialFlags: u8 {
+        const DOUBLE_SIDED = 0x1;
+        const ALPHA_TEST = 0x2;
+    }
+}
+
+#[derive(Debug)]
+#[tiger_type(id = 0x80801111, diff)]
+pub struct SMaterial {
+    pub blend_mode: EBlendMode,
+    pub flags: MaterialFlags,
+    pub _pad: tiger_parse::Padding<2>,
+    pub constants: [f32; 2],
+}
+
+#[derive(Debug)]
+#[tiger_type(id = 0x80802222, diff)]
+pub struct SMesh {
+    pub vertex_count: u32,
+    pub material: Tag<SMaterial>,
+}
+
+#[derive(Debug)]
+#[tiger_type(id = 0x33333333, diff)]
+pub struct SLight {
+    pub range: f32,
+}
+
+#[derive(Debug)]
+#[tiger_type(id = 0x44444444, diff)]
+pub struct SDecal {
+    pub scale: f32,
+    #[tiger(skip_diff)]
+    pub seed: u32,
+}
+
+#[derive(Debug)]
+#[tiger_variant(diff)]
+pub enum SComponent {
+    Light(SLight),
+    Decal(SDecal),
+}
+
+fn material_data(blend_mode: u8, flags: u8, constants: [f32; 2]) -> Vec<u8> {
+    [
+        &[blend_mode, flags, 0, 0][..],
+        &constants[0].to_le_bytes(),
+        &constants[1].to_le_bytes(),
+    ]
+    .concat()
+}
+
+fn source(vertex_count: u32, material: Vec<u8>) -> MemoryTagSource {
+    let mesh = TagHash::new(1, 0);
+    let material_tag = TagHash::new(1, 1);
+
+    let mut source = MemoryTagSource::new(Endian::Little);
+    source.insert(
+        mesh,
+        TagEntry {
+            reference: 0x80802222,
+            file_type: 8,
+            file_subtype: 0,
+        },
+        [vertex_count.to_le_bytes(), material_tag.0.to_le_bytes()].concat(),
+    );
+    source.insert(
+        material_tag,
+        TagEntry {
+            reference: 0x80801111,
+            file_type: 8,
+            file_subtype: 0,
+        },
+        material,
+    );
+
+    source
+}
+
+fn main() {
+    let old = source(24, material_data(0, 0x1, [1.0, 0.5]));
+    let new = source(36, material_data(1, 0x3, [1.0, 0.25]));
+
+    let root = TagHash::new(1, 0);
+    let changes = diff::diff_tree::<SMesh>(&old, root, &new, root).unwrap();
+    for change in &changes {
+        println!("{change}");
+    }
+
+    let changes: Vec<String> = changes.iter().map(|c| c.to_string()).collect();
+    assert_eq!(
+        changes,
+        [
+            "vertex_count: 24 -> 36",
+            "material.blend_mode: Opaque -> Additive",
+            "material.flags: 0x1 -> 0x3",
+            "material.constants[1]: 0.5 -> 0.25",
+        ]
+    );
+
+    // Unknown enum values are compared by value
+    let unknown = TagHash::new(1, 1);
+    let mut new = source(24, material_data(7, 0x1, [1.0, 0.5]));
+    let changes = diff::diff_tree::<SMaterial>(&old, unknown, &new, unknown).unwrap();
+    assert_eq!(changes[0].to_string(), "blend_mode: Opaque -> 7 (0x7)");
+
+    // Referenced tags that fail to read are reported in the diff
+    new.insert(
+        unknown,
+        TagEntry {
+            reference: 0x80801111,
+            file_type: 8,
+            file_subtype: 0,
+        },
+        [0u8; 2],
+    );
+    let changes = diff::diff_tree::<SMesh>(&old, root, &new, root).unwrap();
+    assert!(matches!(changes[0].change, Change::ReadFailed(_)));
+
+    let light = SComponent::Light(SLight { range: 4.0 });
+    let changes = diff::diff(&light, &SComponent::Light(SLight { range: 8.0 }));
+    assert_eq!(changes[0].to_string(), "(Light).range: 4.0 -> 8.0");
+
+    let decal = SComponent::Decal(SDecal {
+        scale: 1.0,
+        seed: 1,
+    });
+    let changes = diff::diff(&light, &decal);
+    assert_eq!(
+        changes[0].change,
+        Change::Variant {
+            old: "Light",
+            new: "Decal",
+            old_class: 0x33333333,
+            new_class: 0x44444444,
+        }
+    );
+
+    // Skipped fields are never compared
+    let reseeded = SComponent::Decal(SDecal {
+        scale: 1.0,
+        seed: 2,
+    });
+    assert!(diff::diff(&decal, &reseeded).is_empty());
+}
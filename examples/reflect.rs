@@ -100,6 +100,15 @@ mod structs {
         pub unk6: u8,
     }
 
+    #[derive(Debug, Clone)]
+    #[tiger_type(id = 0x80808081)]
+    pub struct SRange<T> {
+        pub start: T,
+        pub end: T,
+    }
+
+    tiger_parse::reflect_instance!(SRange<u16>, SRange<glam::Vec3>);
+
     #[derive(Debug, Clone)]
     #[tiger_type(id = 0x80808080, size = 0x10)]
     pub struct Test {
@@ -121,6 +130,59 @@ fn main() {
         println!("{}", cc.format(s));
         println!("{s}");
     }
+
+    // Fields of type parameters are resolved for each registered instantiation
+    let range = STRUCTS.iter().find(|s| s.name == "SRange<u16>").unwrap();
+    assert!(matches!(
+        range.fields[0].ty,
+        tiger_parse::reflect::ReflectedType::UInt16
+    ));
+
+    // Map the differences between two versions of a tag back to their fields
+    let mesh_data = STRUCTS
+        .iter()
+        .find(|s| s.name == "SStaticMeshData")
+        .unwrap();
+    use tiger_parse::TigerReadable;
+
+    // Array counts and offsets are 32-bit with the 32bit feature
+    let offset_size = tiger_parse::Pointer::<u32>::SIZE;
+    let count_size = Vec::<u32>::SIZE - offset_size;
+    let parts = mesh_data
+        .fields
+        .iter()
+        .find(|f| f.name == "parts")
+        .unwrap()
+        .offset;
+
+    // The parts block directly follows the struct, with one part after the block header
+    let block = mesh_data.size;
+    let mut old = vec![0u8; block];
+    old[parts..parts + count_size].copy_from_slice(&1u64.to_le_bytes()[..count_size]);
+    let offset_pos = parts + count_size;
+    old[offset_pos..offset_pos + offset_size]
+        .copy_from_slice(&((block - offset_pos) as u64).to_le_bytes()[..offset_size]);
+    old[0x4C..0x50].copy_from_slice(&1.0f32.to_le_bytes());
+    old.extend_from_slice(&1u64.to_le_bytes()[..count_size]);
+    old.resize(block + 16 + structs::SStaticMeshPart::SIZE, 0);
+
+    let mut new = old.clone();
+    new[0x4C..0x50].copy_from_slice(&2.0f32.to_le_bytes());
+    new[block + 16 + 9] = 1;
+
+    let changes = tiger_parse::byte_diff::diff_bytes(
+        &old,
+        &new,
+        tiger_parse::Endian::Little,
+        mesh_data,
+        &STRUCTS,
+    );
+    for change in &changes {
+        println!("{change}");
+    }
+
+    let paths: Vec<_> = changes.iter().map(|c| c.path.as_deref()).collect();
+    assert_eq!(paths, [Some("mesh_scale"), Some("parts[0].unk9")]);
 }
 
 #[cfg(not(feature = "reflect"))]
@@ -1,6 +1,8 @@
 use std::io::Cursor;
 
-use tiger_parse::{tiger_type, tiger_variant_enum, Endian, Padding, TigerReadable};
+use tiger_parse::{
+    tiger_type, tiger_variant_enum, Endian, InlineVec, Padding, TigerReadable, TigerReader,
+};
 
 tiger_parse::reflection_container!();
 
@@ -118,6 +120,27 @@ pub enum EPrimitiveType {
     TriangleStrip = 5,
 }
 
+#[derive(Debug, PartialEq, Copy, Clone)]
+#[repr(i8)]
+#[tiger_type]
+pub enum ETestLenient {
+    A = -1,
+    B,
+    C = 4,
+    #[tiger(unknown)]
+    Unknown(i8),
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+#[repr(u8)]
+#[tiger_type(strict)]
+pub enum ETestStrict {
+    A,
+    B,
+    #[tiger(unknown)]
+    Unknown(u8),
+}
+
 #[derive(Debug, Clone)]
 #[tiger_type(etype = 32, esubtype = 4)]
 pub struct TagHash {
@@ -125,6 +148,15 @@ pub struct TagHash {
     pub value: u32,
 }
 
+/// Accepts several entry types
+#[derive(Debug)]
+#[tiger_type(etype = 32, esubtype = 1)]
+#[tiger_type(etype = 32, esubtype = 2)]
+#[tiger_type(etype = 48)]
+pub struct MultiTypeTag {
+    pub value: u32,
+}
+
 #[tiger_type]
 struct Test(i32, u32);
 
@@ -134,8 +166,84 @@ struct Test2(i32, u8, Substruct);
 #[tiger_type]
 struct Substruct([u32; 4]);
 
+#[derive(Debug)]
+#[tiger_type]
+struct ConditionalTest {
+    flags: u32,
+    #[tiger(if = "flags & 1 != 0")]
+    extra: Option<u32>,
+    #[tiger(if = "flags & 2 != 0")]
+    extra2: Option<u32>,
+    value: u32,
+}
+
+#[derive(Debug)]
+#[tiger_type]
+struct ReservedConditionalTest {
+    flags: u32,
+    #[tiger(if = "flags & 1 != 0", reserve)]
+    extra: Option<u32>,
+    #[tiger(if = "flags & 2 != 0", reserve)]
+    extra2: Option<u32>,
+    value: u32,
+}
+
+#[derive(Debug)]
+#[tiger_type]
+struct CountTest {
+    count: u16,
+    #[tiger(count = "count")]
+    values: Vec<u16>,
+    prefixed: InlineVec<u8, u8>,
+}
+
+fn half_to_f32(half: u16) -> f32 {
+    half as f32 / u16::MAX as f32
+}
+
+fn read_xor_hash(reader: &mut dyn TigerReader, endian: Endian) -> tiger_parse::Result<u32> {
+    Ok(u32::read_ds_endian(reader, endian)? ^ 0x811C9DC5)
+}
+
+/// Doesn't implement `TigerReadable`, only read through `read_rgb`
+#[derive(Debug, PartialEq)]
+struct Rgb(u8, u8, u8);
+
+fn read_rgb(reader: &mut dyn TigerReader, _endian: Endian) -> tiger_parse::Result<Rgb> {
+    let [r, g, b] = <[u8; 3]>::read_ds(reader)?;
+    Ok(Rgb(r, g, b))
+}
+
+#[derive(Debug)]
+#[tiger_type(visit, diff)]
+struct CustomReadTest {
+    #[tiger(map = "half_to_f32")]
+    value: f32,
+    #[tiger(read_with = "read_xor_hash")]
+    hash: u32,
+    #[tiger(read_with = "read_xor_hash", size = 4)]
+    hash_sized: u32,
+    #[tiger(read_with = "read_rgb", size = 3)]
+    color: Rgb,
+    after: u8,
+}
+
+#[derive(Debug)]
+#[tiger_type]
+struct Pair<T> {
+    a: T,
+    b: T,
+}
+
+#[derive(Debug)]
+#[tiger_type]
+struct GenericArray<T: Copy, const N: usize>([T; N]);
+
+tiger_parse::reflect_instance!(Pair<u16>, GenericArray<u8, 3>);
+
 tiger_variant_enum! {
     [Unknown(true)]
+    [impl TigerVisit, TigerDiff]
     enum MapNodeResource {
     }
 }
@@ -148,6 +256,13 @@ pub fn main() {
     println!("{:#x?}", v);
 
     assert_eq!(TagHash::ETYPE, Some((32, Some(4))));
+    assert_eq!(TagHash::ETYPES, [(32, Some(4))]);
+    assert_eq!(MultiTypeTag::ETYPE, Some((32, Some(1))));
+    assert_eq!(
+        MultiTypeTag::ETYPES,
+        [(32, Some(1)), (32, Some(2)), (48, None)]
+    );
+    assert_eq!(u32::ETYPES, []);
 
     const TEST: [u8; 8] = [0xfe, 0xff, 0xff, 0xff, 0x7b, 0x00, 0x00, 0x00];
     let mut cursor = std::io::Cursor::new(&TEST);
@@ -169,4 +284,86 @@ pub fn main() {
     let mut cursor = std::io::Cursor::new(&ENUM_TEST);
     let e: EPrimitiveType = TigerReadable::read_ds(&mut cursor).unwrap();
     assert_eq!(e, EPrimitiveType::Triangles);
+
+    const LENIENT_ENUM_TEST: [u8; 4] = [0xFF, 0x0, 0x4, 0x5];
+    let mut cursor = std::io::Cursor::new(&LENIENT_ENUM_TEST);
+    let e: [ETestLenient; 4] = TigerReadable::read_ds(&mut cursor).unwrap();
+    assert_eq!(
+        e,
+        [
+            ETestLenient::A,
+            ETestLenient::B,
+            ETestLenient::C,
+            ETestLenient::Unknown(5)
+        ]
+    );
+    assert_eq!(ETestLenient::SIZE, 1);
+
+    const STRICT_ENUM_TEST: [u8; 2] = [0x1, 0x2];
+    let mut cursor = std::io::Cursor::new(&STRICT_ENUM_TEST);
+    assert_eq!(ETestStrict::read_ds(&mut cursor).unwrap(), ETestStrict::B);
+    let e = ETestStrict::read_ds(&mut cursor).unwrap_err();
+    println!("Error (this is expected): {e}");
+
+    const SIGNED_ENUM_TEST: [u8; 1] = [0xFE];
+    let mut cursor = std::io::Cursor::new(&SIGNED_ENUM_TEST);
+    let Err(tiger_parse::Error::EnumVariantOutOfRange { value, .. }) =
+        EPrimitiveType::read_ds(&mut cursor)
+    else {
+        panic!("Unexpected success");
+    };
+    assert_eq!(value, "254 (0xFE)");
+
+    const CONDITIONAL_TEST: [u8; 16] = [
+        0x2, 0x0, 0x0, 0x0, 0xFF, 0xFF, 0xFF, 0xFF, 0x7b, 0x00, 0x00, 0x00, 0x1, 0x0, 0x0, 0x0,
+    ];
+    let mut cursor = std::io::Cursor::new(&CONDITIONAL_TEST);
+    // Absent fields aren't stored, the next field is read right after the previous one
+    let c = ConditionalTest::read_ds(&mut cursor).unwrap();
+    assert_eq!(c.extra, None);
+    assert_eq!(c.extra2, Some(0xFFFFFFFF));
+    assert_eq!(c.value, 123);
+    assert_eq!(cursor.position(), 0xC);
+
+    // Absent `reserve`d fields are skipped over
+    cursor.set_position(0);
+    let c = ReservedConditionalTest::read_ds(&mut cursor).unwrap();
+    assert_eq!(c.extra, None);
+    assert_eq!(c.extra2, Some(123));
+    assert_eq!(c.value, 1);
+    assert_eq!(cursor.position(), 0x10);
+    assert_eq!(ReservedConditionalTest::SIZE, 0x10);
+
+    const COUNT_TEST: [u8; 9] = [0x2, 0x0, 0x1, 0x0, 0x2, 0x0, 0x2, 0x3, 0x4];
+    let mut cursor = std::io::Cursor::new(&COUNT_TEST);
+    let c = CountTest::read_ds(&mut cursor).unwrap();
+    assert_eq!(c.values, [1, 2]);
+    assert_eq!(*c.prefixed, [3, 4]);
+    assert_eq!(cursor.position(), 9);
+    const { assert!(CountTest::VARIABLE_SIZE) };
+    const { assert!(ConditionalTest::VARIABLE_SIZE) };
+    const { assert!(!ReservedConditionalTest::VARIABLE_SIZE) };
+
+    assert_eq!(CustomReadTest::SIZE, 2 + 4 + 4 + 3 + 1);
+    const CUSTOM_READ_TEST: [u8; 14] = [
+        0xFF, 0xFF, 0xC5, 0x9D, 0x1C, 0x81, 0xC4, 0x9D, 0x1C, 0x81, 0x1, 0x2, 0x3, 0x7,
+    ];
+    let mut cursor = std::io::Cursor::new(&CUSTOM_READ_TEST);
+    let c = CustomReadTest::read_ds(&mut cursor).unwrap();
+    assert_eq!(c.value, 1.0);
+    assert_eq!(c.hash, 0);
+    assert_eq!(c.hash_sized, 1);
+    assert_eq!(c.color, Rgb(1, 2, 3));
+    assert_eq!(c.after, 7);
+
+    assert_eq!(<Pair<u16>>::SIZE, 4);
+    assert_eq!(<Pair<glam::Vec3>>::SIZE, 24);
+    assert_eq!(<GenericArray<u16, 3>>::SIZE, 6);
+    const GENERIC_TEST: [u8; 4] = [0x1, 0x0, 0x2, 0x0];
+    let mut cursor = std::io::Cursor::new(&GENERIC_TEST);
+    let p = <Pair<u16>>::read_ds(&mut cursor).unwrap();
+    assert_eq!((p.a, p.b), (1, 2));
+    let mut cursor = std::io::Cursor::new(&GENERIC_TEST);
+    let a = <GenericArray<u8, 3>>::read_ds(&mut cursor).unwrap();
+    assert_eq!(a.0, [1, 0, 2]);
 }
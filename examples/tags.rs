@@ -0,0 +1,88 @@
+use tiger_parse::{
+    source::{MemoryTagSource, TagEntry},
+    tiger_type, DataTagHeader, Endian, PackageManagerExt, Tag,
+};
+use tiger_pkg::TagHash;
+
+tiger_parse::reflection_container!();
+
+#[derive(Debug)]
+#[tiger_type(etype = 32, esubtype = 1, size = 0xC, visit)]
+pub struct SIndexBufferHeader {
+    pub data_size: u32,
+    pub is_32bit: u8,
+    #[tiger(offset = 0x8)]
+    #[tiger(data_tag)]
+    pub data: TagHash,
+}
+
+#[derive(Debug)]
+#[tiger_type(id = 0x80801234, size = 0x4, visit)]
+pub struct SMesh {
+    pub index_buffer: Tag<SIndexBufferHeader>,
+}
+
+fn main() {
+    let mesh_tag = TagHash::new(1, 0);
+    let header = TagHash::new(1, 1);
+    let data = TagHash::new(1, 2);
+
+    let mut source = MemoryTagSource::new(Endian::Little);
+    source.insert(
+        mesh_tag,
+        TagEntry {
+            reference: 0x80801234,
+            file_type: 8,
+            file_subtype: 0,
+        },
+        header.0.to_le_bytes(),
+    );
+
+    let mut header_data = vec![0u8; 0xC];
+    header_data[0..4].copy_from_slice(&6u32.to_le_bytes());
+    header_data[8..0xC].copy_from_slice(&data.0.to_le_bytes());
+    source.insert(
+        header,
+        TagEntry {
+            reference: u32::MAX,
+            file_type: 32,
+            file_subtype: 1,
+        },
+        header_data,
+    );
+    source.insert(
+        data,
+        TagEntry {
+            reference: u32::MAX,
+            file_type: 40,
+            file_subtype: 0,
+        },
+        [0, 0, 1, 0, 2, 0],
+    );
+
+    let mesh: SMesh = source.read_tag_struct(mesh_tag).unwrap();
+    let index_buffer = mesh.index_buffer.load(&source).unwrap();
+    println!("{index_buffer:#x?}");
+    assert_eq!(index_buffer.data_tag(), Some(data));
+
+    let indices: Vec<u16> = source.read_header_elements(&index_buffer).unwrap();
+    assert_eq!(indices, [0, 1, 2]);
+    assert_eq!(source.read_header_data(&index_buffer).unwrap().len(), 6);
+
+    #[cfg(feature = "visit")]
+    {
+        let graph = source.dependency_graph::<SMesh>(mesh_tag).unwrap();
+        for tag in graph.tags() {
+            for dependency in graph.dependencies(tag) {
+                println!(
+                    "{tag} -> {} ({})",
+                    dependency.reference.hash, dependency.path
+                );
+            }
+        }
+
+        assert_eq!(graph.dependencies(mesh_tag)[0].target, Some(header));
+        assert_eq!(graph.dependencies(header)[0].path.to_string(), "data");
+        assert_eq!(graph.tags(), [mesh_tag, header, data]);
+    }
+}
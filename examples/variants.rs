@@ -3,12 +3,15 @@ use tiger_parse::*;
 tiger_parse::reflection_container!();
 
 fn main() {
+    #[derive(Debug)]
     #[tiger_type(id = 0x11111111)]
     struct VariantA;
 
+    #[derive(Debug)]
     #[tiger_type(id = 0x22222222)]
     struct VariantB;
 
+    #[derive(Debug)]
     #[tiger_type(id = 0x33333333)]
     struct VariantC;
 
@@ -32,4 +35,189 @@ fn main() {
     println!("a.class_name = {}", a.class_name());
     println!("b.class_name = {}", b.class_name());
     println!("c.class_name = {}", c.class_name());
+
+    #[derive(Debug)]
+    #[tiger_type]
+    struct UnnamedVariant(u32);
+
+    /// Variant enum declared with the attribute macro
+    #[derive(Debug)]
+    #[tiger_variant(offset = 0x10)]
+    pub(crate) enum TestAttributeEnum {
+        /// Variant named differently from its struct
+        First(Box<VariantA>),
+        Second(VariantB),
+        #[tiger(id = 0x44444444)]
+        Unnamed(UnnamedVariant),
+        #[tiger(unknown)]
+        Unknown {
+            class: u32,
+            offset: u64,
+        },
+    }
+
+    let a = TestAttributeEnum::First(Box::new(VariantA));
+    let b = TestAttributeEnum::Second(VariantB);
+    assert_eq!(a.class_id(), 0x11111111);
+    assert_eq!(b.class_id(), 0x22222222);
+    assert!(a.class_name().ends_with("VariantA"));
+    if let TestAttributeEnum::First(inner) = &a {
+        println!("a = {inner:?}");
+    }
+    assert_eq!(TestAttributeEnum::EXTRA_OFFSET, 16);
+
+    const DATA: [u8; 4] = [0x7b, 0x00, 0x00, 0x00];
+    let mut cursor = std::io::Cursor::new(&DATA);
+    let v =
+        TestAttributeEnum::read_variant_endian(&mut cursor, Endian::Little, 0x44444444).unwrap();
+    let TestAttributeEnum::Unnamed(UnnamedVariant(123)) = v else {
+        panic!("Unexpected variant");
+    };
+    assert_eq!(v.class_id(), 0x44444444);
+
+    let v =
+        TestAttributeEnum::read_variant_endian(&mut cursor, Endian::Little, 0x55555555).unwrap();
+    let TestAttributeEnum::Unknown { class, offset } = v else {
+        panic!("Unexpected variant");
+    };
+    assert_eq!((class, offset), (0x55555555, 4));
+    assert_eq!(v.class_name(), "Unknown");
+
+    #[tiger_variant(unknown_size = 0x8)]
+    enum TestCaptureEnum {
+        Second(VariantB),
+        #[tiger(unknown)]
+        Unknown {
+            class: u32,
+            offset: u64,
+            parent_tag: u32,
+            data: Vec<u8>,
+        },
+    }
+
+    const CAPTURE_DATA: [u8; 16] = [
+        0x55, 0x55, 0x55, 0x55, 0x78, 0x56, 0x34, 0x12, 0x1, 0x2, 0x3, 0x4, 0x22, 0x22, 0x22, 0x22,
+    ];
+    let mut cursor = std::io::Cursor::new(&CAPTURE_DATA);
+    cursor.set_position(4);
+    let captured =
+        TestCaptureEnum::read_variant_endian(&mut cursor, Endian::Little, 0x55555555).unwrap();
+    let TestCaptureEnum::Unknown {
+        class,
+        offset,
+        parent_tag,
+        data,
+    } = captured
+    else {
+        panic!("Unexpected variant");
+    };
+    assert_eq!((class, offset, parent_tag), (0x55555555, 4, 0x12345678));
+    assert_eq!(data, [0x78, 0x56, 0x34, 0x12, 0x1, 0x2, 0x3, 0x4]);
+
+    cursor.set_position(8);
+    let captured =
+        TestCaptureEnum::read_variant_endian(&mut cursor, Endian::Little, 0x55555555).unwrap();
+    let TestCaptureEnum::Unknown { data, .. } = captured else {
+        panic!("Unexpected variant");
+    };
+    assert_eq!(data, [0x1, 0x2, 0x3, 0x4]);
+
+    /// Dispatches on the class type of the class header instead of the resource type
+    #[derive(Debug)]
+    #[tiger_variant(dispatch = "class")]
+    enum TestClassEnum {
+        #[tiger(class = 0x80801234)]
+        Value(UnnamedVariant),
+        #[tiger(unknown)]
+        Unknown {
+            class: u32,
+            offset: u64,
+            parent_tag: u32,
+        },
+    }
+
+    #[derive(Debug)]
+    #[tiger_variant(dispatch = "combined")]
+    enum TestCombinedEnum {
+        #[tiger(id = 0xAAAAAAAA, class = 0x80801234)]
+        Value(UnnamedVariant),
+        #[tiger(unknown)]
+        Unknown {
+            class: u32,
+            class_type: u32,
+            offset: u64,
+        },
+    }
+
+    // Pointer, resource type, parent tag, class type and the resource itself
+    let mut class_data = vec![0u8; 0x1C];
+    let pointer_size = VariantPointer::<TestClassEnum>::SIZE;
+    class_data[..pointer_size].copy_from_slice(&0x10u64.to_le_bytes()[..pointer_size]);
+    class_data[0xC..0x10].copy_from_slice(&0xAAAAAAAAu32.to_le_bytes());
+    class_data[0x10..0x14].copy_from_slice(&0x12345678u32.to_le_bytes());
+    class_data[0x14..0x18].copy_from_slice(&0x80801234u32.to_le_bytes());
+    class_data[0x18..0x1C].copy_from_slice(&123u32.to_le_bytes());
+
+    assert_eq!(TestClassEnum::EXTRA_OFFSET, 8);
+    let mut cursor = std::io::Cursor::new(&class_data);
+    let v: VariantPointer<TestClassEnum> =
+        TigerReadable::read_ds_endian(&mut cursor, Endian::Little).unwrap();
+    let TestClassEnum::Value(UnnamedVariant(123)) = *v else {
+        panic!("Unexpected variant {v:?}");
+    };
+    assert_eq!(v.class_id(), 0x80801234);
+
+    let mut cursor = std::io::Cursor::new(&class_data);
+    let v: VariantPointer<TestCombinedEnum> =
+        TigerReadable::read_ds_endian(&mut cursor, Endian::Little).unwrap();
+    let TestCombinedEnum::Value(UnnamedVariant(123)) = *v else {
+        panic!("Unexpected variant {v:?}");
+    };
+    assert_eq!(v.class_id(), 0xAAAAAAAA);
+
+    // Same resource type, but a class type unknown to both enums
+    class_data[0x14..0x18].copy_from_slice(&0x80809999u32.to_le_bytes());
+    let mut cursor = std::io::Cursor::new(&class_data);
+    let v: VariantPointer<TestClassEnum> =
+        TigerReadable::read_ds_endian(&mut cursor, Endian::Little).unwrap();
+    let TestClassEnum::Unknown {
+        class,
+        offset,
+        parent_tag,
+    } = *v
+    else {
+        panic!("Unexpected variant {v:?}");
+    };
+    assert_eq!((class, offset, parent_tag), (0x80809999, 0x18, 0x12345678));
+
+    let mut cursor = std::io::Cursor::new(&class_data);
+    let v: VariantPointer<TestCombinedEnum> =
+        TigerReadable::read_ds_endian(&mut cursor, Endian::Little).unwrap();
+    let TestCombinedEnum::Unknown {
+        class,
+        class_type,
+        offset,
+    } = *v
+    else {
+        panic!("Unexpected variant {v:?}");
+    };
+    assert_eq!((class, class_type, offset), (0xAAAAAAAA, 0x80809999, 0x18));
+
+    /// Class types that are FNV-1a hashes of the class name
+    #[derive(Debug)]
+    #[tiger_variant(dispatch = "class")]
+    enum TestHashedClassEnum {
+        #[tiger(class_name = "SValue")]
+        Value(UnnamedVariant),
+    }
+
+    const VALUE_CLASS: HashedName = HashedName::new("SValue");
+    cursor.set_position(0x18);
+    let v = TestHashedClassEnum::read_variant_endian(&mut cursor, Endian::Little, VALUE_CLASS.0)
+        .unwrap();
+    let TestHashedClassEnum::Value(UnnamedVariant(value)) = &v;
+    assert_eq!(*value, 123);
+    assert_eq!(v.class_id(), fnv1a32(b"SValue"));
+    let classes: NameDictionary = ["SValue"].into_iter().collect();
+    assert_eq!(HashedName(v.class_id()).resolve(&classes), Some("SValue"));
 }
@@ -0,0 +1,110 @@
+use glam::Vec3;
+use tiger_parse::{
+    tiger_type, tiger_variant,
+    visit::{self, FieldPath},
+};
+
+tiger_parse::reflection_container!();
+
+#[derive(Debug, Clone)]
+#[tiger_type(id = 0x11111111, visit)]
+pub struct SLight {
+    pub position: Vec3,
+    pub range: f32,
+}
+
+#[derive(Debug, Clone)]
+#[tiger_type(id = 0x22222222, visit)]
+pub struct SDecal {
+    pub position: Vec3,
+    pub scale: Vec3,
+}
+
+#[derive(Debug)]
+#[tiger_variant(visit)]
+pub enum SEntityComponent {
+    Light(SLight),
+    Decal(Box<SDecal>),
+}
+
+#[derive(Debug)]
+#[tiger_type(visit)]
+pub struct SEntity {
+    pub origin: Vec3,
+    #[tiger(skip_visit)]
+    pub pivot: Vec3,
+    pub lights: Vec<SLight>,
+    pub decal: Box<SDecal>,
+    pub bounds: (Vec3, Vec3),
+}
+
+fn main() {
+    let mut entity = SEntity {
+        origin: Vec3::ZERO,
+        pivot: Vec3::ZERO,
+        lights: vec![
+            SLight {
+                position: Vec3::X,
+                range: 4.0,
+            },
+            SLight {
+                position: Vec3::NEG_X,
+                range: 2.0,
+            },
+        ],
+        decal: Box::new(SDecal {
+            position: Vec3::Y,
+            scale: Vec3::ONE,
+        }),
+        bounds: (Vec3::NEG_ONE, Vec3::ONE),
+    };
+
+    let positions: Vec<(String, Vec3)> = visit::collect::<Vec3, _>(&entity)
+        .into_iter()
+        .map(|(path, v)| (path.to_string(), v))
+        .collect();
+    for (path, position) in &positions {
+        println!("{path} = {position}");
+    }
+
+    assert_eq!(
+        positions
+            .iter()
+            .map(|(p, _)| p.as_str())
+            .collect::<Vec<_>>(),
+        [
+            "origin",
+            "lights[0].position",
+            "lights[1].position",
+            "decal.position",
+            "decal.scale",
+            "bounds[0]",
+            "bounds[1]",
+        ]
+    );
+
+    let total_range: f32 = visit::collect::<SLight, _>(&entity)
+        .iter()
+        .map(|(_, light)| light.range)
+        .sum();
+    assert_eq!(total_range, 6.0);
+
+    // Move everything but the bounds up
+    visit::for_each_mut(&mut entity, |path: &FieldPath, v: &mut Vec3| {
+        if path.to_string().ends_with("position") {
+            *v += Vec3::Z;
+        }
+    });
+    assert_eq!(entity.lights[0].position, Vec3::new(1.0, 0.0, 1.0));
+    assert_eq!(entity.bounds.0, Vec3::NEG_ONE);
+
+    let component = SEntityComponent::Decal(Box::new(SDecal {
+        position: Vec3::ZERO,
+        scale: Vec3::ONE,
+    }));
+    let paths: Vec<String> = visit::collect::<Vec3, _>(&component)
+        .into_iter()
+        .map(|(path, _)| path.to_string())
+        .collect();
+    assert_eq!(paths, ["(Decal).position", "(Decal).scale"]);
+}
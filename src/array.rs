@@ -2,6 +2,7 @@ use crate::{ResultExt, TigerReadable, TigerReader};
 
 impl<T: TigerReadable, const N: usize> TigerReadable for [T; N] {
     fn read_ds_endian(reader: &mut dyn TigerReader, endian: crate::Endian) -> crate::Result<Self> {
+        const { assert!(!T::VARIABLE_SIZE, "{}", VARIABLE_SIZE_MESSAGE) };
         let mut data: Self = unsafe { std::mem::zeroed() };
         for (i, v) in data.iter_mut().enumerate() {
             unsafe {
@@ -12,9 +13,16 @@ impl<T: TigerReadable, const N: usize> TigerReadable for [T; N] {
         Ok(data)
     }
 
-    const SIZE: usize = N * T::SIZE;
+    const SIZE: usize = {
+        assert!(!T::VARIABLE_SIZE, "{}", VARIABLE_SIZE_MESSAGE);
+        N * T::SIZE
+    };
 }
 
+/// Array elements are laid out with a fixed stride, which variable-size types don't have
+pub(crate) const VARIABLE_SIZE_MESSAGE: &str =
+    "Variable-size types can't be used as array elements";
+
 #[cfg(test)]
 mod tests {
     use super::*;
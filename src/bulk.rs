@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use tiger_pkg::TagHash;
+
+/// Results of reading every tag of a type, see [`PackageManagerExt::read_all_tag_structs`](crate::PackageManagerExt::read_all_tag_structs)
+pub struct BulkRead<T> {
+    results: Vec<(TagHash, crate::Result<T>)>,
+    stats: BulkReadStats,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BulkReadStats {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Number of failures per error kind (see [`Error::kind`](crate::Error::kind))
+    pub failures_by_kind: HashMap<&'static str, usize>,
+}
+
+impl BulkReadStats {
+    /// Fraction of tags that were read successfully, between 0 and 1
+    pub fn success_rate(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+
+        self.succeeded as f32 / self.total as f32
+    }
+}
+
+impl<T> BulkRead<T> {
+    /// Collects the results, sorted by tag
+    pub fn new(mut results: Vec<(TagHash, crate::Result<T>)>) -> Self {
+        results.sort_by_key(|(tag, _)| *tag);
+
+        let mut stats = BulkReadStats {
+            total: results.len(),
+            ..Default::default()
+        };
+        for (_, result) in &results {
+            match result {
+                Ok(_) => stats.succeeded += 1,
+                Err(e) => {
+                    stats.failed += 1;
+                    *stats.failures_by_kind.entry(e.kind()).or_default() += 1;
+                }
+            }
+        }
+
+        Self { results, stats }
+    }
+
+    pub fn stats(&self) -> &BulkReadStats {
+        &self.stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(TagHash, crate::Result<T>)> {
+        self.results.iter()
+    }
+
+    /// Iterates over the successfully read tags
+    pub fn successes(&self) -> impl Iterator<Item = (TagHash, &T)> {
+        self.results
+            .iter()
+            .filter_map(|(tag, result)| result.as_ref().ok().map(|v| (*tag, v)))
+    }
+
+    /// Iterates over the tags that failed to read
+    pub fn failures(&self) -> impl Iterator<Item = (TagHash, &crate::Error)> {
+        self.results
+            .iter()
+            .filter_map(|(tag, result)| result.as_ref().err().map(|e| (*tag, e)))
+    }
+}
+
+impl<T> IntoIterator for BulkRead<T> {
+    type Item = (TagHash, crate::Result<T>);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.results.into_iter()
+    }
+}
+
+impl<T> std::fmt::Debug for BulkRead<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BulkRead")
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reads all given tags, in parallel when the `parallel` feature is enabled
+pub(crate) fn read_all<T: Send>(
+    tags: Vec<TagHash>,
+    read: impl Fn(TagHash) -> crate::Result<T> + Sync,
+) -> BulkRead<T> {
+    #[cfg(feature = "parallel")]
+    let results = {
+        use rayon::prelude::*;
+        tags.into_par_iter().map(|tag| (tag, read(tag))).collect()
+    };
+
+    #[cfg(not(feature = "parallel"))]
+    let results = tags.into_iter().map(|tag| (tag, read(tag))).collect();
+
+    BulkRead::new(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use tiger_pkg::TagHash;
+
+    use super::read_all;
+    use crate::error::Error;
+
+    #[test]
+    fn test_read_all() {
+        let tags = (0..16).map(|i| TagHash::new(1, i)).collect();
+        let results = read_all(tags, |tag| match tag.entry_index() % 4 {
+            0 => Err(Error::PointerNull),
+            1 => Err(Error::InvalidStructure("test".to_string())),
+            i => Ok(i),
+        });
+
+        let stats = results.stats();
+        assert_eq!((stats.total, stats.succeeded, stats.failed), (16, 8, 8));
+        assert_eq!(stats.failures_by_kind["PointerNull"], 4);
+        assert_eq!(stats.failures_by_kind["InvalidStructure"], 4);
+        assert_eq!(stats.success_rate(), 0.5);
+
+        let tags: Vec<TagHash> = results.iter().map(|(tag, _)| *tag).collect();
+        assert!(tags.is_sorted());
+        assert_eq!(results.successes().count(), 8);
+    }
+}
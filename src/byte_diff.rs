@@ -0,0 +1,521 @@
+//! Byte-level diffs of raw tag data, mapped back to the fields of a reflected struct.
+//!
+//! Unlike [`TigerDiff`](crate::diff), this works on the raw buffers, so changes to unknown fields
+//! and padding show up even when the struct definition is incomplete.
+
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+    io::Cursor,
+    ops::Range,
+};
+
+use crate::{
+    reflect::{ReflectedStruct, ReflectedType},
+    Endian, Offset, Size, TigerReadable,
+};
+
+/// Nesting limit when following `Vec` and `Pointer` blocks
+const MAX_DEPTH: usize = 32;
+
+/// Size of the header preceding the elements of a `Vec` block (element count and class)
+const ARRAY_HEADER_SIZE: usize = 16;
+
+/// Range of bytes that differs between two buffers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ByteChange {
+    /// Path of the field covering the changed bytes, or `None` for bytes not covered by any field
+    pub path: Option<String>,
+    pub range: Range<usize>,
+    /// Old bytes in `range`, shorter than the range if the old buffer ends within it
+    pub old: Vec<u8>,
+    /// New bytes in `range`, shorter than the range if the new buffer ends within it
+    pub new: Vec<u8>,
+}
+
+impl Display for ByteChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let hex = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|b| format!("{b:02X}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        write!(
+            f,
+            "{} @ 0x{:X}..0x{:X}: [{}] -> [{}]",
+            self.path.as_deref().unwrap_or("<unmapped>"),
+            self.range.start,
+            self.range.end,
+            hex(&self.old),
+            hex(&self.new)
+        )
+    }
+}
+
+/// Field covering a range of bytes in a buffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldRegion {
+    pub path: String,
+    pub range: Range<usize>,
+}
+
+/// Returns the ranges covered by the fields of `root`, read from the start of `data`.
+///
+/// Struct fields are looked up by name in `structs`, usually the `STRUCTS` slice declared by
+/// [`reflection_container!`](crate::reflection_container). `Vec` and `Pointer` fields are followed
+/// into their blocks, the `Vec` header and block header are attributed to the `Vec` field itself.
+/// Inline arrays declared with `#[tiger(count = "...")]` and conditional fields without `reserve` can't be
+/// resolved and are not mapped.
+pub fn field_layout(
+    data: &[u8],
+    endian: Endian,
+    root: &ReflectedStruct,
+    structs: &[ReflectedStruct],
+) -> Vec<FieldRegion> {
+    let mut layout = LayoutBuilder {
+        data,
+        endian,
+        structs,
+        regions: vec![],
+        followed_blocks: HashSet::new(),
+        depth: 0,
+    };
+    layout.walk_struct(root, 0, "");
+
+    let mut regions = layout.regions;
+    regions.sort_by_key(|r| (r.range.start, r.range.end));
+    regions
+}
+
+/// Compares two buffers holding `root` and maps every changed byte to the field covering it.
+///
+/// Changes within the new buffer are mapped using the layout of the new buffer, bytes past its end
+/// (when the buffer shrunk) using the layout of the old buffer.
+pub fn diff_bytes(
+    old: &[u8],
+    new: &[u8],
+    endian: Endian,
+    root: &ReflectedStruct,
+    structs: &[ReflectedStruct],
+) -> Vec<ByteChange> {
+    let new_layout = field_layout(new, endian, root, structs);
+    let old_layout = field_layout(old, endian, root, structs);
+
+    let region_at = |offset: usize| -> Option<&FieldRegion> {
+        let layout = if offset < new.len() {
+            &new_layout
+        } else {
+            &old_layout
+        };
+
+        let index = layout.partition_point(|r| r.range.start <= offset);
+        layout[..index]
+            .iter()
+            .rev()
+            .find(|r| r.range.contains(&offset))
+    };
+
+    let mut changes: Vec<ByteChange> = vec![];
+    for offset in 0..old.len().max(new.len()) {
+        if old.get(offset) == new.get(offset) {
+            continue;
+        }
+
+        let path = region_at(offset).map(|r| &r.path);
+        match changes.last_mut() {
+            Some(last) if last.range.end == offset && last.path.as_ref() == path => {
+                last.range.end += 1;
+            }
+            _ => changes.push(ByteChange {
+                path: path.cloned(),
+                range: offset..offset + 1,
+                old: vec![],
+                new: vec![],
+            }),
+        }
+    }
+
+    for change in &mut changes {
+        let slice = |data: &[u8]| {
+            data.get(change.range.start.min(data.len())..change.range.end.min(data.len()))
+                .unwrap_or_default()
+                .to_vec()
+        };
+        change.old = slice(old);
+        change.new = slice(new);
+    }
+
+    changes
+}
+
+struct LayoutBuilder<'a> {
+    data: &'a [u8],
+    endian: Endian,
+    structs: &'a [ReflectedStruct],
+    regions: Vec<FieldRegion>,
+    /// Blocks that were already followed, so overlapping pointers don't map the same data twice
+    followed_blocks: HashSet<usize>,
+    depth: usize,
+}
+
+impl LayoutBuilder<'_> {
+    fn find_struct(&self, name: &str) -> Option<&ReflectedStruct> {
+        self.structs.iter().find(|s| s.name == name)
+    }
+
+    fn walk_struct(&mut self, s: &ReflectedStruct, base: usize, path: &str) {
+        // Fields after a variable-size field aren't at their static offset, so their bytes are left unmapped
+        let mut static_offsets = true;
+        for field in s.fields.iter() {
+            static_offsets |= field.explicit_offset;
+            if !static_offsets {
+                continue;
+            }
+
+            // Inline arrays and conditional fields without reserved space can't be located without reading them
+            if field.count.is_some() || (field.condition.is_some() && !field.reserve) {
+                static_offsets = false;
+                continue;
+            }
+
+            let field_path = if path.is_empty() {
+                field.name.to_string()
+            } else {
+                format!("{path}.{}", field.name)
+            };
+            self.walk_type(&field.ty, base + field.offset, field.size, field_path);
+            static_offsets = !field.variable_size;
+        }
+    }
+
+    fn walk_type(&mut self, ty: &ReflectedType, offset: usize, size: usize, path: String) {
+        if self.depth >= MAX_DEPTH {
+            return;
+        }
+
+        self.depth += 1;
+        match ty {
+            ReflectedType::FixedArray(count, inner) if *count > 0 => {
+                let element_size = size / count;
+                for i in 0..*count {
+                    self.walk_type(
+                        inner,
+                        offset + i * element_size,
+                        element_size,
+                        format!("{path}[{i}]"),
+                    );
+                }
+            }
+            ReflectedType::Tuple(elements) => {
+                let sizes: Option<Vec<usize>> =
+                    elements.iter().map(|ty| self.type_size(ty)).collect();
+                match sizes {
+                    Some(sizes) => {
+                        let mut element_offset = offset;
+                        for (i, (ty, size)) in elements.iter().zip(sizes).enumerate() {
+                            self.walk_type(ty, element_offset, size, format!("{path}[{i}]"));
+                            element_offset += size;
+                        }
+                    }
+                    None => self.push(offset, size, path),
+                }
+            }
+            ReflectedType::Array(inner) => {
+                self.push(offset, size, path.clone());
+                self.walk_array(inner, offset, path);
+            }
+            ReflectedType::Pointer(inner) | ReflectedType::PointerOptional(inner) => {
+                self.push(offset, size, path.clone());
+                self.walk_pointer(inner, offset, path);
+            }
+            ReflectedType::StringPointer => {
+                self.push(offset, size, path.clone());
+                self.walk_string_pointer(offset, path);
+            }
+            ReflectedType::Other(name) => match self.find_struct(name) {
+                Some(s) => {
+                    let s = s.clone();
+                    self.walk_struct(&s, offset, &path);
+                }
+                None => self.push(offset, size, path),
+            },
+            _ => self.push(offset, size, path),
+        }
+        self.depth -= 1;
+    }
+
+    fn walk_array(&mut self, inner: &ReflectedType, offset: usize, path: String) {
+        let (Some(count), Some(relative)) = (
+            self.read::<Size>(offset),
+            self.read::<Offset>(offset + std::mem::size_of::<Size>()),
+        ) else {
+            return;
+        };
+
+        let Some(element_size) = self.type_size(inner) else {
+            return;
+        };
+
+        #[allow(clippy::unnecessary_cast)] // Offset is i32 with the 32bit feature
+        let block = (offset + std::mem::size_of::<Size>()) as i64 + relative as i64;
+        if count <= 0 || block < 0 || !self.followed_blocks.insert(block as usize) {
+            return;
+        }
+
+        let block = block as usize;
+        let count = count as usize;
+        let block_end = count
+            .checked_mul(element_size)
+            .and_then(|size| size.checked_add(block + ARRAY_HEADER_SIZE));
+        if block_end.is_none_or(|end| end > self.data.len()) {
+            return;
+        }
+
+        self.push(block, ARRAY_HEADER_SIZE, path.clone());
+        for i in 0..count {
+            self.walk_type(
+                inner,
+                block + ARRAY_HEADER_SIZE + i * element_size,
+                element_size,
+                format!("{path}[{i}]"),
+            );
+        }
+    }
+
+    fn walk_pointer(&mut self, inner: &ReflectedType, offset: usize, path: String) {
+        let Some(relative) = self.read::<Offset>(offset) else {
+            return;
+        };
+
+        if relative == 0 || relative == Offset::MAX {
+            return;
+        }
+
+        #[allow(clippy::unnecessary_cast)] // Offset is i32 with the 32bit feature
+        let target = offset as i64 + relative as i64;
+        let Some(size) = self.type_size(inner) else {
+            return;
+        };
+
+        if target < 0 || !self.followed_blocks.insert(target as usize) {
+            return;
+        }
+
+        // Pointers are transparent, the target is mapped to the path of the pointer itself
+        self.walk_type(inner, target as usize, size, path);
+    }
+
+    fn walk_string_pointer(&mut self, offset: usize, path: String) {
+        let Some(relative) = self.read::<Offset>(offset) else {
+            return;
+        };
+
+        if relative == 0 || relative == Offset::MAX {
+            return;
+        }
+
+        #[allow(clippy::unnecessary_cast)] // Offset is i32 with the 32bit feature
+        let target = offset as i64 + relative as i64;
+        if target < 0 || !self.followed_blocks.insert(target as usize) {
+            return;
+        }
+
+        // The string bytes, including the terminator, are mapped to the path of the pointer
+        let target = target as usize;
+        if let Some(length) = self
+            .data
+            .get(target..)
+            .and_then(|s| s.iter().position(|&b| b == 0))
+        {
+            self.push(target, length + 1, path);
+        }
+    }
+
+    fn type_size(&self, ty: &ReflectedType) -> Option<usize> {
+        Some(match ty {
+            ReflectedType::UInt8 | ReflectedType::Int8 => 1,
+            ReflectedType::UInt16 | ReflectedType::Int16 => 2,
+            ReflectedType::UInt32
+            | ReflectedType::Int32
+            | ReflectedType::Float32
+            | ReflectedType::TagHash
+            | ReflectedType::LocalizedString
+            | ReflectedType::HashedName => 4,
+            ReflectedType::UInt64 | ReflectedType::Int64 | ReflectedType::Float64 => 8,
+            ReflectedType::Vec2 => 8,
+            ReflectedType::Vec3 => 12,
+            ReflectedType::Vec4 => 16,
+            ReflectedType::Padding(size) => *size,
+            ReflectedType::Tuple(elements) => elements
+                .iter()
+                .map(|ty| self.type_size(ty))
+                .sum::<Option<usize>>()?,
+            ReflectedType::Array(_) => std::mem::size_of::<(Size, Offset)>(),
+            ReflectedType::Pointer(_)
+            | ReflectedType::PointerOptional(_)
+            | ReflectedType::StringPointer => std::mem::size_of::<Offset>(),
+            ReflectedType::FixedArray(count, inner) => count * self.type_size(inner)?,
+            ReflectedType::Other(name) => self.find_struct(name)?.size,
+        })
+    }
+
+    fn push(&mut self, offset: usize, size: usize, path: String) {
+        let end = (offset + size).min(self.data.len());
+        if offset < end {
+            self.regions.push(FieldRegion {
+                path,
+                range: offset..end,
+            });
+        }
+    }
+
+    fn read<T: TigerReadable>(&self, offset: usize) -> Option<T> {
+        let data = self.data.get(offset..offset.checked_add(T::SIZE)?)?;
+        T::read_ds_endian(&mut Cursor::new(data), self.endian).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::diff_bytes;
+    use crate::{
+        reflect::{CowBox, ReflectedField, ReflectedStruct, ReflectedType},
+        Endian, Offset, Size,
+    };
+
+    const fn field(
+        name: &'static str,
+        offset: usize,
+        size: usize,
+        ty: ReflectedType,
+    ) -> ReflectedField {
+        ReflectedField {
+            name: Cow::Borrowed(name),
+            size,
+            offset,
+            explicit_offset: false,
+            condition: None,
+            reserve: false,
+            count: None,
+            variable_size: false,
+            ty,
+        }
+    }
+
+    #[test]
+    fn test_diff_bytes() {
+        static ELEMENT_FIELDS: [ReflectedField; 2] = [
+            field("value", 0, 2, ReflectedType::UInt16),
+            field("unk2", 2, 2, ReflectedType::UInt16),
+        ];
+        static ROOT_FIELDS: [ReflectedField; 3] = [
+            field("unk0", 0, 4, ReflectedType::UInt32),
+            field("scale", 4, 4, ReflectedType::Float32),
+            field(
+                "elements",
+                8,
+                std::mem::size_of::<(Size, Offset)>(),
+                ReflectedType::Array(CowBox::Borrowed(&ReflectedType::Other(Cow::Borrowed(
+                    "Element",
+                )))),
+            ),
+        ];
+        let structs = [ReflectedStruct {
+            id: 0,
+            name: Cow::Borrowed("Element"),
+            fields: Cow::Borrowed(&ELEMENT_FIELDS),
+            is_tuple: false,
+            size: 4,
+        }];
+        let root = ReflectedStruct {
+            id: 0,
+            name: Cow::Borrowed("Root"),
+            fields: Cow::Borrowed(&ROOT_FIELDS),
+            is_tuple: false,
+            size: 8 + std::mem::size_of::<(Size, Offset)>(),
+        };
+
+        // Root, then a block with two elements directly after it
+        let mut old = vec![0u8; root.size];
+        let count_end = 8 + std::mem::size_of::<Size>();
+        old[8..count_end].copy_from_slice(&(2 as Size).to_le_bytes());
+        old[count_end..root.size]
+            .copy_from_slice(&((root.size - count_end) as Offset).to_le_bytes());
+        old.extend_from_slice(&(2 as Size).to_le_bytes());
+        old.resize(root.size + 16, 0);
+        old.extend_from_slice(&[1, 0, 2, 0, 3, 0, 4, 0]);
+
+        let mut new = old.clone();
+        new[4..8].copy_from_slice(&2.0f32.to_le_bytes());
+        new[root.size + 16 + 6] = 5;
+        new.push(0xFF);
+
+        let changes: Vec<String> = diff_bytes(&old, &new, Endian::Little, &root, &structs)
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "scale @ 0x7..0x8: [00] -> [40]".to_string(),
+                format!(
+                    "elements[1].unk2 @ 0x{:X}..0x{:X}: [04] -> [05]",
+                    root.size + 22,
+                    root.size + 23
+                ),
+                format!(
+                    "<unmapped> @ 0x{:X}..0x{:X}: [] -> [FF]",
+                    root.size + 24,
+                    root.size + 25
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_bytes_variable_size() {
+        let fields = vec![
+            ReflectedField {
+                variable_size: true,
+                ..field(
+                    "values",
+                    0,
+                    1,
+                    ReflectedType::Other(Cow::Borrowed("InlineVec")),
+                )
+            },
+            field("after", 1, 1, ReflectedType::UInt8),
+            ReflectedField {
+                explicit_offset: true,
+                ..field("fixed", 4, 1, ReflectedType::UInt8)
+            },
+        ];
+        let root = ReflectedStruct {
+            id: 0,
+            name: Cow::Borrowed("Root"),
+            fields: Cow::Owned(fields),
+            is_tuple: false,
+            size: 8,
+        };
+
+        let old = [0u8; 8];
+        let mut new = old;
+        new[0] = 1;
+        new[1] = 1;
+        new[4] = 1;
+
+        let paths: Vec<_> = diff_bytes(&old, &new, Endian::Little, &root, &[])
+            .into_iter()
+            .map(|c| c.path)
+            .collect();
+        assert_eq!(
+            paths,
+            [Some("values".to_string()), None, Some("fixed".to_string())]
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use std::{
+    any::{Any, TypeId},
+    num::NonZeroUsize,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use lru::LruCache;
+use tiger_pkg::TagHash;
+
+/// Cache for parsed tags, keyed by tag hash and parsed type.
+///
+/// Once the cache holds `capacity` entries, the least recently used entry is evicted for every new entry.
+pub struct TagCache {
+    capacity: Option<usize>,
+    inner: Mutex<CacheInner>,
+}
+
+struct CacheInner {
+    /// Kept in order of use, so the least recently used entry is evicted in constant time
+    entries: LruCache<(TagHash, TypeId), Arc<dyn Any + Send + Sync>>,
+    stats: TagCacheStats,
+}
+
+impl CacheInner {
+    fn new(capacity: Option<usize>) -> Self {
+        let entries = match capacity.and_then(NonZeroUsize::new) {
+            Some(capacity) => LruCache::new(capacity),
+            // A zero capacity never stores anything, see `TagCache::get_or_read`
+            None => LruCache::unbounded(),
+        };
+
+        Self {
+            entries,
+            stats: TagCacheStats::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TagCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub invalidations: u64,
+}
+
+impl TagCache {
+    /// Creates a cache holding at most `capacity` parsed tags
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            inner: Mutex::new(CacheInner::new(Some(capacity))),
+        }
+    }
+
+    /// Creates a cache without a size limit
+    pub fn unbounded() -> Self {
+        Self {
+            capacity: None,
+            inner: Mutex::new(CacheInner::new(None)),
+        }
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Returns the cached value for `tag`, or parses it with `read` and caches the result.
+    ///
+    /// Errors are returned as-is and are not cached. The lock is not held while `read` runs,
+    /// so concurrent misses for the same tag may parse it more than once.
+    pub fn get_or_read<T: Any + Send + Sync>(
+        &self,
+        tag: TagHash,
+        read: impl FnOnce() -> crate::Result<T>,
+    ) -> crate::Result<Arc<T>> {
+        if let Some(value) = self.get(tag) {
+            return Ok(value);
+        }
+
+        let value = Arc::new(read()?);
+
+        if self.capacity == Some(0) {
+            return Ok(value);
+        }
+
+        let mut inner = self.lock();
+        let key = (tag, TypeId::of::<T>());
+        if let Some(existing) = inner.entries.get(&key) {
+            // Another thread parsed the same tag in the meantime, share its value
+            return Ok(Arc::clone(existing)
+                .downcast()
+                .expect("Cache entry type mismatch"));
+        }
+
+        // Only returns an entry when the least recently used one was evicted, as the key is new
+        if inner.entries.push(key, value.clone()).is_some() {
+            inner.stats.evictions += 1;
+        }
+
+        Ok(value)
+    }
+
+    /// Returns the cached value for `tag` parsed as `T`, if any
+    pub fn get<T: Any + Send + Sync>(&self, tag: TagHash) -> Option<Arc<T>> {
+        let mut inner = self.lock();
+        let value = inner.entries.get(&(tag, TypeId::of::<T>())).cloned();
+
+        match value {
+            Some(value) => {
+                inner.stats.hits += 1;
+                Some(value.downcast().expect("Cache entry type mismatch"))
+            }
+            None => {
+                inner.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Removes all cached values for `tag`, regardless of type
+    pub fn invalidate(&self, tag: TagHash) {
+        let mut inner = self.lock();
+        let keys: Vec<_> = inner
+            .entries
+            .iter()
+            .map(|(key, _)| *key)
+            .filter(|(hash, _)| *hash == tag)
+            .collect();
+        for key in keys {
+            inner.entries.pop(&key);
+            inner.stats.invalidations += 1;
+        }
+    }
+
+    /// Removes the cached value for `tag` parsed as `T`
+    pub fn invalidate_type<T: Any>(&self, tag: TagHash) {
+        let mut inner = self.lock();
+        if inner.entries.pop(&(tag, TypeId::of::<T>())).is_some() {
+            inner.stats.invalidations += 1;
+        }
+    }
+
+    /// Removes all cached values
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        inner.stats.invalidations += inner.entries.len() as u64;
+        inner.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn stats(&self) -> TagCacheStats {
+        self.lock().stats
+    }
+
+    pub fn reset_stats(&self) {
+        self.lock().stats = TagCacheStats::default();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheInner> {
+        // The cache is never left in an inconsistent state, so a poisoned lock is safe to reuse
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for TagCache {
+    fn default() -> Self {
+        Self::unbounded()
+    }
+}
+
+impl std::fmt::Debug for TagCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TagCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tiger_pkg::TagHash;
+
+    use super::{TagCache, TagCacheStats};
+    use crate::error::Error;
+
+    #[test]
+    fn test_tag_cache() -> crate::Result<()> {
+        let cache = TagCache::new(2);
+        let a = TagHash(0x80800001);
+        let b = TagHash(0x80800002);
+        let c = TagHash(0x80800003);
+
+        let first = cache.get_or_read(a, || Ok(1u32))?;
+        let second = cache.get_or_read(a, || -> crate::Result<u32> { unreachable!() })?;
+        assert!(Arc::ptr_eq(&first, &second));
+
+        // Same tag, different type
+        assert_eq!(*cache.get_or_read(a, || Ok(2u64))?, 2);
+        assert_eq!(cache.len(), 2);
+
+        // Evicts the u32 entry for `a`, which was used least recently
+        cache.get_or_read(b, || Ok(3u32))?;
+        assert!(cache.get::<u32>(a).is_none());
+        assert!(cache.get::<u64>(a).is_some());
+
+        assert!(cache
+            .get_or_read(c, || -> crate::Result<u32> { Err(Error::PointerNull) })
+            .is_err());
+        assert!(cache.get::<u32>(c).is_none());
+
+        cache.invalidate(a);
+        assert_eq!(cache.len(), 1);
+
+        assert_eq!(
+            cache.stats(),
+            TagCacheStats {
+                hits: 2,
+                misses: 6,
+                evictions: 1,
+                invalidations: 1,
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,565 @@
+//! Structural diffs between two parsed values, such as the same tag from two game versions.
+//!
+//! `tiger_type` structs declared with `#[tiger_type(diff)]` implement [`TigerDiff`] when the `diff` feature is enabled,
+//! as do `#[tiger_variant(diff)]` enums. Hand-written `TigerReadable` types used as fields need an implementation as well,
+//! see [`diff_value`] for types implementing `PartialEq` and `Debug`. Fields can be left out with `#[tiger(skip_diff)]`,
+//! fields read with `read_with` or `map` are always left out.
+
+use std::fmt::{Debug, Display, Formatter};
+
+#[cfg(feature = "tiger_pkg")]
+use std::collections::HashSet;
+
+#[cfg(feature = "tiger_pkg")]
+use tiger_pkg::{TagHash, TagHash64};
+
+use crate::{
+    pointer::{
+        LazyResourcePointer, Pointer, PointerOptional, ResourcePointer, TypedResourcePointer,
+        TypedResourcePointerOptional,
+    },
+    variant::{OptionalVariantPointer, VariantEnum, VariantPointer},
+    visit::{FieldPath, PathSegment},
+    BoundedNullString, FixedString, FixedWideString, HashedName, InlineVec, LocalizedString,
+    NullString, Padding, StringPointer, TigerReadable, WideString,
+};
+#[cfg(feature = "tiger_pkg")]
+use crate::{source::TagSource, PackageManagerExt};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// A value changed, both values are formatted with `Debug`
+    Value { old: String, new: String },
+    /// The length of a `Vec` changed. The elements past the shorter length are reported as added or removed
+    Length { old: usize, new: usize },
+    /// The value only exists in the new version
+    Added,
+    /// The value only exists in the old version
+    Removed,
+    /// A variant enum holds a different variant, identified by the variant names
+    Variant {
+        old: &'static str,
+        new: &'static str,
+        old_class: u32,
+        new_class: u32,
+    },
+    /// A referenced tag could not be read while diffing a tag tree
+    ReadFailed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub path: FieldPath,
+    pub change: Change,
+}
+
+impl Display for FieldChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let path = if self.path.is_empty() {
+            "<root>".to_string()
+        } else {
+            self.path.to_string()
+        };
+
+        match &self.change {
+            Change::Value { old, new } => write!(f, "{path}: {old} -> {new}"),
+            Change::Length { old, new } => write!(f, "{path}: length {old} -> {new}"),
+            Change::Added => write!(f, "{path}: added"),
+            Change::Removed => write!(f, "{path}: removed"),
+            Change::Variant {
+                old,
+                new,
+                old_class,
+                new_class,
+            } => write!(
+                f,
+                "{path}: variant {old} (0x{old_class:08X}) -> {new} (0x{new_class:08X})"
+            ),
+            Change::ReadFailed(e) => write!(f, "{path}: failed to read tag: {e}"),
+        }
+    }
+}
+
+/// State of a running diff, holding the current path and the changes found so far
+pub struct DiffContext<'a> {
+    path: FieldPath,
+    changes: Vec<FieldChange>,
+
+    /// Old and new sources, used to follow typed tag references
+    #[cfg(feature = "tiger_pkg")]
+    sources: Option<(&'a dyn TagSource, &'a dyn TagSource)>,
+    #[cfg(feature = "tiger_pkg")]
+    visited: HashSet<(TagHash, TagHash)>,
+
+    #[cfg(not(feature = "tiger_pkg"))]
+    _marker: std::marker::PhantomData<&'a ()>,
+}
+
+impl DiffContext<'_> {
+    fn new() -> Self {
+        Self {
+            path: FieldPath::default(),
+            changes: vec![],
+            #[cfg(feature = "tiger_pkg")]
+            sources: None,
+            #[cfg(feature = "tiger_pkg")]
+            visited: HashSet::new(),
+            #[cfg(not(feature = "tiger_pkg"))]
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Path of the values currently being compared
+    pub fn path(&self) -> &FieldPath {
+        &self.path
+    }
+
+    /// Records a change at the current path
+    pub fn push(&mut self, change: Change) {
+        self.changes.push(FieldChange {
+            path: self.path.clone(),
+            change,
+        });
+    }
+
+    /// Diffs `old` and `new` under `segment`
+    pub fn child<T: TigerDiff + ?Sized>(&mut self, segment: PathSegment, old: &T, new: &T) {
+        self.path.push(segment);
+        old.diff(new, self);
+        self.path.pop();
+    }
+}
+
+pub trait TigerDiff {
+    /// Records the changes from `self` to `new` in `ctx`
+    fn diff(&self, new: &Self, ctx: &mut DiffContext);
+}
+
+/// Returns the changes from `old` to `new`
+pub fn diff<T: TigerDiff + ?Sized>(old: &T, new: &T) -> Vec<FieldChange> {
+    let mut ctx = DiffContext::new();
+    old.diff(new, &mut ctx);
+    ctx.changes
+}
+
+/// Reads `old_tag` and `new_tag` as `T` and returns the changes between them,
+/// including changes in tags referenced through [`Tag`](crate::Tag) and [`Tag64`](crate::Tag64).
+///
+/// Changes in referenced tags are reported under the path of the referencing field.
+/// Failing to read either root tag is an error, referenced tags that fail to read are reported as [`Change::ReadFailed`].
+#[cfg(feature = "tiger_pkg")]
+pub fn diff_tree<T: TigerReadable + TigerDiff>(
+    old_source: &dyn TagSource,
+    old_tag: TagHash,
+    new_source: &dyn TagSource,
+    new_tag: TagHash,
+) -> crate::Result<Vec<FieldChange>> {
+    let old: T = old_source.read_tag_struct(old_tag)?;
+    let new: T = new_source.read_tag_struct(new_tag)?;
+
+    let mut ctx = DiffContext::new();
+    ctx.sources = Some((old_source, new_source));
+    ctx.visited.insert((old_tag, new_tag));
+    old.diff(&new, &mut ctx);
+
+    Ok(ctx.changes)
+}
+
+/// Compares two values with `PartialEq`, recording a [`Change::Value`] if they differ
+pub fn diff_value<T: PartialEq + Debug + ?Sized>(old: &T, new: &T, ctx: &mut DiffContext) {
+    if old != new {
+        ctx.push(Change::Value {
+            old: format!("{old:?}"),
+            new: format!("{new:?}"),
+        });
+    }
+}
+
+macro_rules! impl_diff_value {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl TigerDiff for $ty {
+                fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+                    diff_value(self, new, ctx);
+                }
+            }
+        )+
+    };
+}
+
+impl_diff_value! {
+    (), bool, u8, u16, u32, u64, u128, i8, i16, i32, i64, i128,
+    glam::Vec2, glam::Vec3, glam::Vec4, glam::IVec2, glam::IVec3, glam::IVec4, glam::Quat, glam::Mat4,
+}
+
+#[cfg(feature = "tiger_pkg")]
+impl_diff_value!(TagHash, TagHash64);
+
+// Floats are compared bitwise, so NaNs with the same bits are equal
+macro_rules! impl_diff_float {
+    ($($ty:ty),+) => {
+        $(
+            impl TigerDiff for $ty {
+                fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+                    if self.to_bits() != new.to_bits() {
+                        ctx.push(Change::Value {
+                            old: format!("{self:?}"),
+                            new: format!("{new:?}"),
+                        });
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_diff_float!(f32, f64);
+
+macro_rules! impl_diff_string {
+    ($($ty:ident $(<const $param:ident>)?),+) => {
+        $(
+            impl$(<const $param: usize>)? TigerDiff for $ty$(<$param>)? {
+                fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+                    diff_value(&self.0, &new.0, ctx);
+                }
+            }
+        )+
+    };
+}
+
+impl_diff_string!(
+    NullString,
+    BoundedNullString<const MAX>,
+    FixedString<const N>,
+    WideString,
+    FixedWideString<const N>
+);
+
+/// Only the string is compared, its offset depends on the layout of the whole tag
+impl TigerDiff for StringPointer {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        diff_value(&self.0, &new.0, ctx);
+    }
+}
+
+impl TigerDiff for LocalizedString {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        diff_value(self, new, ctx);
+    }
+}
+
+impl TigerDiff for HashedName {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        diff_value(self, new, ctx);
+    }
+}
+
+/// Padding is never reported
+impl<const N: usize> TigerDiff for Padding<N> {
+    fn diff(&self, _new: &Self, _ctx: &mut DiffContext) {}
+}
+
+// Pointer offsets depend on the layout of the whole tag, so only the pointed-to resources are compared
+
+impl TigerDiff for ResourcePointer {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        diff_value(
+            &(self.is_valid, self.resource_type),
+            &(new.is_valid, new.resource_type),
+            ctx,
+        );
+    }
+}
+
+#[cfg(feature = "tiger_pkg")]
+impl TigerDiff for crate::ResourcePointerWithClass {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        diff_value(
+            &(self.is_valid, self.resource_type, self.class_type),
+            &(new.is_valid, new.resource_type, new.class_type),
+            ctx,
+        );
+    }
+}
+
+impl<T: TigerReadable> TigerDiff for LazyResourcePointer<T> {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        self.pointer.diff(&new.pointer, ctx);
+    }
+}
+
+impl<T: TigerReadable + TigerDiff> TigerDiff for Pointer<T> {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        self.0.diff(&new.0, ctx);
+    }
+}
+
+impl<T: TigerReadable + TigerDiff> TigerDiff for PointerOptional<T> {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        self.0.diff(&new.0, ctx);
+    }
+}
+
+impl<T: TigerReadable + TigerDiff> TigerDiff for TypedResourcePointer<T> {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        self.0.diff(&new.0, ctx);
+    }
+}
+
+impl<T: TigerReadable + TigerDiff> TigerDiff for TypedResourcePointerOptional<T> {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        self.0.diff(&new.0, ctx);
+    }
+}
+
+impl<T: VariantEnum + TigerDiff> TigerDiff for VariantPointer<T> {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        self.0.diff(&new.0, ctx);
+    }
+}
+
+impl<T: VariantEnum + TigerDiff> TigerDiff for OptionalVariantPointer<T> {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        self.0.diff(&new.0, ctx);
+    }
+}
+
+impl<T: TigerDiff + ?Sized> TigerDiff for Box<T> {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        (**self).diff(new, ctx);
+    }
+}
+
+impl<T: TigerDiff> TigerDiff for Option<T> {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        match (self, new) {
+            (Some(old), Some(new)) => old.diff(new, ctx),
+            (None, Some(_)) => ctx.push(Change::Added),
+            (Some(_), None) => ctx.push(Change::Removed),
+            (None, None) => {}
+        }
+    }
+}
+
+impl<T: TigerDiff> TigerDiff for [T] {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        if self.len() != new.len() {
+            ctx.push(Change::Length {
+                old: self.len(),
+                new: new.len(),
+            });
+        }
+
+        for (i, (old, new)) in self.iter().zip(new).enumerate() {
+            ctx.child(PathSegment::Index(i), old, new);
+        }
+
+        let common = self.len().min(new.len());
+        let extra = if self.len() > new.len() {
+            Change::Removed
+        } else {
+            Change::Added
+        };
+        for i in common..self.len().max(new.len()) {
+            ctx.path.push(PathSegment::Index(i));
+            ctx.push(extra.clone());
+            ctx.path.pop();
+        }
+    }
+}
+
+impl<T: TigerDiff> TigerDiff for Vec<T> {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        self.as_slice().diff(new.as_slice(), ctx);
+    }
+}
+
+impl<T: TigerDiff, const N: usize> TigerDiff for [T; N] {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        self.as_slice().diff(new.as_slice(), ctx);
+    }
+}
+
+impl<C, T: TigerDiff> TigerDiff for InlineVec<C, T> {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        self.0.diff(&new.0, ctx);
+    }
+}
+
+macro_rules! tuple_diff_impls {
+    ( $( $name:ident $index:tt )+ ) => {
+        impl<$($name: TigerDiff),+> TigerDiff for ($($name,)+) {
+            fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+                $(
+                    ctx.child(PathSegment::Index($index), &self.$index, &new.$index);
+                )+
+            }
+        }
+    };
+}
+
+tuple_diff_impls! { A 0 }
+tuple_diff_impls! { A 0 B 1 }
+tuple_diff_impls! { A 0 B 1 C 2 }
+tuple_diff_impls! { A 0 B 1 C 2 D 3 }
+tuple_diff_impls! { A 0 B 1 C 2 D 3 E 4 }
+tuple_diff_impls! { A 0 B 1 C 2 D 3 E 4 F 5 }
+tuple_diff_impls! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 }
+tuple_diff_impls! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 }
+
+/// Compares the hashes of two typed tag references. When diffing a tag tree,
+/// the referenced tags are read from their sources and compared as well.
+#[cfg(feature = "tiger_pkg")]
+fn diff_tag_reference<T: TigerReadable + TigerDiff, H: PartialEq + Debug>(
+    old: (&H, Option<TagHash>),
+    new: (&H, Option<TagHash>),
+    ctx: &mut DiffContext,
+) {
+    diff_value(old.0, new.0, ctx);
+
+    let (Some((old_source, new_source)), (_, Some(old_tag)), (_, Some(new_tag))) =
+        (ctx.sources, old, new)
+    else {
+        return;
+    };
+
+    if !ctx.visited.insert((old_tag, new_tag)) {
+        return;
+    }
+
+    let old = old_source.read_tag_struct::<T>(old_tag);
+    let new = new_source.read_tag_struct::<T>(new_tag);
+    match (old, new) {
+        (Ok(old), Ok(new)) => old.diff(&new, ctx),
+        (Err(e), _) | (_, Err(e)) => ctx.push(Change::ReadFailed(e.to_string())),
+    }
+}
+
+#[cfg(feature = "tiger_pkg")]
+impl<T: TigerReadable + TigerDiff> TigerDiff for crate::Tag<T> {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        let resolve = |tag: &Self| tag.is_some().then(|| tag.hash());
+        diff_tag_reference::<T, _>(
+            (&self.hash(), resolve(self)),
+            (&new.hash(), resolve(new)),
+            ctx,
+        );
+    }
+}
+
+#[cfg(feature = "tiger_pkg")]
+impl<T: TigerReadable + TigerDiff> TigerDiff for crate::Tag64<T> {
+    fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+        let resolve = |tag: &Self, source: Option<&dyn TagSource>| match source {
+            Some(source) if tag.is_some() => source.resolve_tag64(tag.hash()),
+            _ => None,
+        };
+        let old_tag = resolve(self, ctx.sources.map(|(old, _)| old));
+        let new_tag = resolve(new, ctx.sources.map(|(_, new)| new));
+        diff_tag_reference::<T, _>((&self.hash(), old_tag), (&new.hash(), new_tag), ctx);
+    }
+}
+
+/// Implements `TigerDiff` for enums declared with `tiger_variant_enum!`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tiger_variant_enum_diff {
+    ($enum_name:ident { $($variant:ident),* }) => {
+        impl $crate::diff::TigerDiff for $enum_name {
+            fn diff(&self, new: &Self, ctx: &mut $crate::diff::DiffContext) {
+                #[allow(unreachable_patterns)]
+                match (self, new) {
+                    $(
+                        (Self::$variant(old), Self::$variant(new)) => ctx.child(
+                            $crate::visit::PathSegment::Variant(stringify!($variant)),
+                            old,
+                            new,
+                        ),
+                    )*
+                    _ => {
+                        #[allow(unreachable_patterns)]
+                        let name = |v: &Self| match v {
+                            $(Self::$variant(_) => stringify!($variant),)*
+                            _ => "Unknown",
+                        };
+                        $crate::diff::diff_variant(
+                            (name(self), self.class_id()),
+                            (name(new), new.class_id()),
+                            ctx,
+                        )
+                    }
+                }
+            }
+        }
+    };
+}
+
+/// Records a [`Change::Variant`] if the variant names or classes differ, used by generated implementations
+#[doc(hidden)]
+pub fn diff_variant(
+    (old, old_class): (&'static str, u32),
+    (new, new_class): (&'static str, u32),
+    ctx: &mut DiffContext,
+) {
+    if old != new || old_class != new_class {
+        ctx.push(Change::Variant {
+            old,
+            new,
+            old_class,
+            new_class,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, Change, DiffContext, FieldChange, TigerDiff};
+    use crate::visit::PathSegment;
+
+    struct Node {
+        value: f32,
+        children: Vec<(u8, Option<u16>)>,
+    }
+
+    impl TigerDiff for Node {
+        fn diff(&self, new: &Self, ctx: &mut DiffContext) {
+            ctx.child(PathSegment::Field("value"), &self.value, &new.value);
+            ctx.child(
+                PathSegment::Field("children"),
+                &self.children,
+                &new.children,
+            );
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = Node {
+            value: f32::NAN,
+            children: vec![(1, Some(2)), (3, None)],
+        };
+        let new = Node {
+            value: f32::NAN,
+            children: vec![(1, Some(3)), (3, Some(4)), (5, None)],
+        };
+
+        assert!(diff(&old, &old).is_empty());
+
+        let changes = diff(&old, &new);
+        let changes: Vec<String> = changes.iter().map(FieldChange::to_string).collect();
+        assert_eq!(
+            changes,
+            [
+                "children: length 2 -> 3",
+                "children[0][1]: 2 -> 3",
+                "children[1][1]: added",
+                "children[2]: added",
+            ]
+        );
+
+        assert_eq!(
+            diff(&new, &old)[0].change,
+            Change::Length { old: 3, new: 2 }
+        );
+    }
+}
@@ -1,9 +1,19 @@
-use std::io::Cursor;
+use std::{any::Any, io::Cursor, sync::Arc};
 
 use tiger_pkg::{TagHash, TagHash64, Version};
 
-use crate::{error::Error, TigerReadable, TigerReader};
+use crate::{
+    bulk::BulkRead,
+    cache::TagCache,
+    error::Error,
+    source::{TagEntry, TagSource},
+    TigerReadable, TigerReader,
+};
 
+#[cfg(feature = "visit")]
+use crate::visit::{DependencyGraph, TigerVisit};
+
+/// Typed tag reads, implemented for every [`TagSource`]
 pub trait PackageManagerExt {
     fn read_tag_struct<T: TigerReadable>(&self, tag: impl Into<TagHash>) -> crate::Result<T>;
 
@@ -13,63 +23,102 @@ pub trait PackageManagerExt {
         &self,
         tag_name: impl AsRef<str>,
     ) -> crate::Result<T>;
+
+    /// Same as [`Self::read_tag_struct`], sharing parsed tags through `cache`
+    fn read_tag_struct_cached<T: TigerReadable + Any + Send + Sync>(
+        &self,
+        cache: &TagCache,
+        tag: impl Into<TagHash>,
+    ) -> crate::Result<Arc<T>> {
+        let tag = tag.into();
+        cache.get_or_read(tag, || self.read_tag_struct(tag))
+    }
+
+    /// Same as [`Self::read_tag64_struct`], sharing parsed tags through `cache`
+    fn read_tag64_struct_cached<T: TigerReadable + Any + Send + Sync>(
+        &self,
+        cache: &TagCache,
+        hash: impl Into<TagHash64>,
+    ) -> crate::Result<Arc<T>>;
+
+    /// Same as [`Self::read_named_tag_struct`], sharing parsed tags through `cache`
+    fn read_named_tag_struct_cached<T: TigerReadable + Any + Send + Sync>(
+        &self,
+        cache: &TagCache,
+        tag_name: impl AsRef<str>,
+    ) -> crate::Result<Arc<T>>;
+
+    /// Reads every tag matching `T::ID`, or `T::ETYPE` for types without an ID.
+    ///
+    /// Tags are parsed in parallel when the `parallel` feature is enabled.
+    fn read_all_tag_structs<T: TigerReadable + Send>(&self) -> crate::Result<BulkRead<T>>;
+
+    /// Returns the data tag referenced by the entry of a header tag, such as a texture or buffer header
+    fn data_tag_of(&self, tag: impl Into<TagHash>) -> crate::Result<TagHash>;
+
+    /// Reads a header tag along with the raw data tag referenced by its entry
+    fn read_tag_with_data<T: TigerReadable>(
+        &self,
+        tag: impl Into<TagHash>,
+    ) -> crate::Result<(T, Vec<u8>)>;
+
+    /// Reads a header tag along with the data tag referenced by its entry, as elements of `D`
+    fn read_tag_with_elements<T: TigerReadable, D: TigerReadable>(
+        &self,
+        tag: impl Into<TagHash>,
+    ) -> crate::Result<(T, Vec<D>)>;
+
+    /// Reads the raw data tag referenced by the `#[tiger(data_tag)]` field of a header
+    fn read_header_data(&self, header: &impl DataTagHeader) -> crate::Result<Vec<u8>>;
+
+    /// Reads the data tag referenced by the `#[tiger(data_tag)]` field of a header, as elements of `D`
+    fn read_header_elements<D: TigerReadable>(
+        &self,
+        header: &impl DataTagHeader,
+    ) -> crate::Result<Vec<D>>;
+
+    /// Reads `root` as `T` and collects every tag it references, following typed [`Tag`](crate::Tag) references recursively.
+    ///
+    /// Failing to read the root tag is an error, failures to read referenced tags are recorded in the graph.
+    #[cfg(feature = "visit")]
+    fn dependency_graph<T: TigerReadable + TigerVisit>(
+        &self,
+        root: impl Into<TagHash>,
+    ) -> crate::Result<DependencyGraph>
+    where
+        Self: Sized;
+}
+
+/// Header tags whose data is stored in a separate tag, implemented for structs with a `#[tiger(data_tag)]` field
+pub trait DataTagHeader {
+    /// Returns the data tag of this header, or `None` if it has no data
+    fn data_tag(&self) -> Option<TagHash>;
+}
+
+#[doc(hidden)]
+pub fn valid_data_tag(tag: impl Into<TagHash>) -> Option<TagHash> {
+    let tag = tag.into();
+    tag.is_some().then_some(tag)
 }
 
-impl PackageManagerExt for tiger_pkg::PackageManager {
+impl<S: TagSource + ?Sized> PackageManagerExt for S {
     fn read_tag_struct<T: TigerReadable>(&self, tag: impl Into<TagHash>) -> crate::Result<T> {
         let tag = tag.into();
 
-        #[cfg(feature = "check_types")]
-        if T::ID.is_some() && (T::ID != Some(u32::MAX) || cfg!(feature = "check_types_strict")) {
-            if let Some(entry) = self.get_entry(tag) {
-                let tag_type = entry.reference;
-                if tag_type != T::ID.unwrap() {
-                    return Err(Error::TypeMismatch(format!(
-                        "Tag type mismatch! Expected 0x{:08X}, got 0x{:08X} (tag {tag}) (type {})",
-                        T::ID.unwrap(),
-                        tag_type,
-                        std::any::type_name::<T>()
-                    )));
-                }
-            }
-        }
+        check_tag_type::<T>(self, tag)?;
 
-        #[cfg(feature = "check_types")]
-        if let Some((etype, esubtype)) = T::ETYPE {
-            if let Some(entry) = self.get_entry(tag) {
-                if etype != entry.file_type {
-                    return Err(Error::TypeMismatch(format!(
-                        "Tag type mismatch! Expected {}:{}, got {}:{} (tag {tag}) (type {})",
-                        etype,
-                        if let Some(subtype) = esubtype {
-                            subtype.to_string()
-                        } else {
-                            "ANY".to_string()
-                        },
-                        entry.file_type,
-                        entry.file_subtype,
-                        std::any::type_name::<T>()
-                    )));
-                }
-            }
-        }
-
-        let data = self
-            .read_tag(tag)
-            .map_err(|e| Error::TagReadFailed(e.to_string()))?;
+        let data = self.read_tag_data(tag)?;
         let mut cursor = Cursor::new(&data);
-        T::read_ds_endian(&mut cursor, self.version.endian().into())
+        T::read_ds_endian(&mut cursor, self.endian())
     }
 
     fn read_tag64_struct<T: TigerReadable>(&self, hash: impl Into<TagHash64>) -> crate::Result<T> {
         let hash = hash.into();
         let tag = self
-            .lookup
-            .tag64_entries
-            .get(&hash.0)
-            .ok_or(Error::Hash64LookupFailed(hash))?
-            .hash32;
+            .resolve_tag64(hash)
+            .ok_or(Error::Hash64LookupFailed(hash))?;
 
+        check_tag64_reference::<T>(self, hash)?;
         self.read_tag_struct(tag)
     }
 
@@ -77,25 +126,293 @@ impl PackageManagerExt for tiger_pkg::PackageManager {
         &self,
         tag_name: impl AsRef<str>,
     ) -> crate::Result<T> {
+        self.read_tag_struct(named_tag::<T>(self, tag_name.as_ref())?)
+    }
+
+    fn read_tag64_struct_cached<T: TigerReadable + Any + Send + Sync>(
+        &self,
+        cache: &TagCache,
+        hash: impl Into<TagHash64>,
+    ) -> crate::Result<Arc<T>> {
+        let hash = hash.into();
         let tag = self
-            .get_named_tag(
-                tag_name.as_ref(),
-                T::ID.ok_or_else(|| {
-                    Error::TypeMismatch(format!(
-                        "Type '{}' does not have a tag ID set",
-                        std::any::type_name::<T>()
-                    ))
-                })?,
-            )
-            .ok_or_else(|| {
+            .resolve_tag64(hash)
+            .ok_or(Error::Hash64LookupFailed(hash))?;
+
+        check_tag64_reference::<T>(self, hash)?;
+        self.read_tag_struct_cached(cache, tag)
+    }
+
+    fn read_named_tag_struct_cached<T: TigerReadable + Any + Send + Sync>(
+        &self,
+        cache: &TagCache,
+        tag_name: impl AsRef<str>,
+    ) -> crate::Result<Arc<T>> {
+        self.read_tag_struct_cached(cache, named_tag::<T>(self, tag_name.as_ref())?)
+    }
+
+    fn read_all_tag_structs<T: TigerReadable + Send>(&self) -> crate::Result<BulkRead<T>> {
+        let tags = if let Some(id) = T::ID.filter(|&id| id != u32::MAX) {
+            self.tags_by_reference(id)
+        } else if !T::ETYPES.is_empty() {
+            let mut tags: Vec<TagHash> = T::ETYPES
+                .iter()
+                .flat_map(|&(etype, esubtype)| self.tags_by_type(etype, esubtype))
+                .collect();
+            tags.sort_unstable();
+            tags.dedup();
+            tags
+        } else {
+            return Err(Error::TypeMismatch(format!(
+                "Type '{}' does not have a tag ID or ETYPE set",
+                std::any::type_name::<T>()
+            )));
+        };
+
+        Ok(crate::bulk::read_all(tags, |tag| self.read_tag_struct(tag)))
+    }
+
+    fn data_tag_of(&self, tag: impl Into<TagHash>) -> crate::Result<TagHash> {
+        let tag = tag.into();
+        let entry = self
+            .entry(tag)
+            .ok_or_else(|| Error::TagReadFailed(format!("No entry found for tag {tag}")))?;
+
+        valid_data_tag(TagHash(entry.reference)).ok_or_else(|| {
+            Error::TagReadFailed(format!(
+                "Tag {tag} does not reference a data tag (reference 0x{:08X})",
+                entry.reference
+            ))
+        })
+    }
+
+    fn read_tag_with_data<T: TigerReadable>(
+        &self,
+        tag: impl Into<TagHash>,
+    ) -> crate::Result<(T, Vec<u8>)> {
+        let tag = tag.into();
+        let data_tag = self.data_tag_of(tag)?;
+        let header = self.read_tag_struct(tag)?;
+
+        Ok((header, self.read_tag_data(data_tag)?))
+    }
+
+    fn read_tag_with_elements<T: TigerReadable, D: TigerReadable>(
+        &self,
+        tag: impl Into<TagHash>,
+    ) -> crate::Result<(T, Vec<D>)> {
+        let tag = tag.into();
+        let data_tag = self.data_tag_of(tag)?;
+        let header = self.read_tag_struct(tag)?;
+
+        Ok((header, read_data_elements(self, data_tag)?))
+    }
+
+    fn read_header_data(&self, header: &impl DataTagHeader) -> crate::Result<Vec<u8>> {
+        self.read_tag_data(header.data_tag().ok_or(Error::PointerNull)?)
+    }
+
+    fn read_header_elements<D: TigerReadable>(
+        &self,
+        header: &impl DataTagHeader,
+    ) -> crate::Result<Vec<D>> {
+        read_data_elements(self, header.data_tag().ok_or(Error::PointerNull)?)
+    }
+
+    #[cfg(feature = "visit")]
+    fn dependency_graph<T: TigerReadable + TigerVisit>(
+        &self,
+        root: impl Into<TagHash>,
+    ) -> crate::Result<DependencyGraph>
+    where
+        Self: Sized,
+    {
+        let root = root.into();
+        let value: T = self.read_tag_struct(root)?;
+
+        Ok(DependencyGraph::build(
+            self,
+            root,
+            crate::visit::collect_tag_references(&value),
+        ))
+    }
+}
+
+/// Reads a data tag as tightly packed elements of `D`
+fn read_data_elements<D: TigerReadable>(
+    source: &(impl TagSource + ?Sized),
+    data_tag: TagHash,
+) -> crate::Result<Vec<D>> {
+    check_tag_type::<D>(source, data_tag)?;
+
+    let data = source.read_tag_data(data_tag)?;
+    if D::SIZE == 0 || data.len() % D::SIZE != 0 {
+        return Err(Error::InvalidStructure(format!(
+            "Size of data tag {data_tag} (0x{:X}) is not a multiple of the size of {} (0x{:X})",
+            data.len(),
+            crate::ShortName::of::<D>(),
+            D::SIZE
+        )));
+    }
+
+    crate::read_inline_vec(
+        &mut Cursor::new(&data),
+        source.endian(),
+        data.len() / D::SIZE,
+    )
+}
+
+/// Checks the entry of a tag against `T::ID` and `T::ETYPES`
+#[allow(unused_variables, clippy::extra_unused_type_parameters)] // Unused without check_types
+fn check_tag_type<T: TigerReadable>(
+    source: &(impl TagSource + ?Sized),
+    tag: TagHash,
+) -> crate::Result<()> {
+    #[cfg(feature = "check_types")]
+    if T::ID.is_some() && (T::ID != Some(u32::MAX) || cfg!(feature = "check_types_strict")) {
+        if let Some(entry) = source.entry(tag) {
+            let tag_type = entry.reference;
+            if tag_type != T::ID.unwrap() {
+                return Err(Error::TypeMismatch(format!(
+                    "Tag type mismatch! Expected 0x{:08X}, got 0x{:08X} (tag {tag}) (type {})",
+                    T::ID.unwrap(),
+                    tag_type,
+                    std::any::type_name::<T>()
+                )));
+            }
+        }
+    }
+
+    #[cfg(feature = "check_types")]
+    if !T::ETYPES.is_empty() {
+        if let Some(entry) = source.entry(tag) {
+            let matches = T::ETYPES.iter().any(|&(etype, esubtype)| {
+                etype == entry.file_type && esubtype.is_none_or(|s| s == entry.file_subtype)
+            });
+
+            if !matches {
+                let expected: Vec<String> = T::ETYPES
+                    .iter()
+                    .map(|(etype, esubtype)| match esubtype {
+                        Some(esubtype) => format!("{etype}:{esubtype}"),
+                        None => format!("{etype}:ANY"),
+                    })
+                    .collect();
+
+                return Err(Error::TypeMismatch(format!(
+                    "Tag type mismatch! Expected {}, got {}:{} (tag {tag}) (type {})",
+                    expected.join(" or "),
+                    entry.file_type,
+                    entry.file_subtype,
+                    std::any::type_name::<T>()
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the reference recorded for a 64-bit tag hash against `T::ID`
+#[allow(unused_variables, clippy::extra_unused_type_parameters)] // Unused without check_types
+fn check_tag64_reference<T: TigerReadable>(
+    source: &(impl TagSource + ?Sized),
+    hash: TagHash64,
+) -> crate::Result<()> {
+    #[cfg(feature = "check_types")]
+    if T::ID.is_some() && (T::ID != Some(u32::MAX) || cfg!(feature = "check_types_strict")) {
+        if let Some(reference) = source.tag64_reference(hash) {
+            if reference != T::ID.unwrap() {
+                return Err(Error::TypeMismatch(format!(
+                    "Tag type mismatch! Expected 0x{:08X}, got 0x{:08X} (tag {hash}) (type {})",
+                    T::ID.unwrap(),
+                    reference,
+                    std::any::type_name::<T>()
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Looks up a named tag with the class ID of `T`
+fn named_tag<T: TigerReadable>(
+    source: &(impl TagSource + ?Sized),
+    tag_name: &str,
+) -> crate::Result<TagHash> {
+    source
+        .named_tag(
+            tag_name,
+            T::ID.ok_or_else(|| {
                 Error::TypeMismatch(format!(
-                    "Tag '{}' with ID 0x{:X} not found",
-                    tag_name.as_ref(),
-                    T::ID.unwrap()
+                    "Type '{}' does not have a tag ID set",
+                    std::any::type_name::<T>()
                 ))
-            })?;
+            })?,
+        )
+        .ok_or_else(|| {
+            Error::TypeMismatch(format!(
+                "Tag '{}' with ID 0x{:X} not found",
+                tag_name,
+                T::ID.unwrap()
+            ))
+        })
+}
 
-        self.read_tag_struct(tag)
+impl TagSource for tiger_pkg::PackageManager {
+    fn entry(&self, tag: TagHash) -> Option<TagEntry> {
+        self.get_entry(tag).map(|entry| TagEntry {
+            reference: entry.reference,
+            file_type: entry.file_type,
+            file_subtype: entry.file_subtype,
+        })
+    }
+
+    fn read_tag_data(&self, tag: TagHash) -> crate::Result<Vec<u8>> {
+        self.read_tag(tag)
+            .map_err(|e| Error::TagReadFailed(e.to_string()))
+    }
+
+    fn endian(&self) -> crate::Endian {
+        self.version.endian().into()
+    }
+
+    fn resolve_tag64(&self, hash: TagHash64) -> Option<TagHash> {
+        self.lookup.tag64_entries.get(&hash.0).map(|e| e.hash32)
+    }
+
+    fn tag64_reference(&self, hash: TagHash64) -> Option<u32> {
+        self.lookup
+            .tag64_entries
+            .get(&hash.0)
+            .map(|e| e.reference.0)
+    }
+
+    fn named_tag(&self, name: &str, class: u32) -> Option<TagHash> {
+        self.get_named_tag(name, class)
+    }
+
+    fn tags(&self) -> Vec<TagHash> {
+        self.lookup
+            .tag32_entries_by_pkg
+            .iter()
+            .flat_map(|(pkg, entries)| (0..entries.len()).map(|i| TagHash::new(*pkg, i as u16)))
+            .collect()
+    }
+
+    fn tags_by_reference(&self, reference: u32) -> Vec<TagHash> {
+        self.get_all_by_reference(reference)
+            .into_iter()
+            .map(|(tag, _)| tag)
+            .collect()
+    }
+
+    fn tags_by_type(&self, etype: u8, esubtype: Option<u8>) -> Vec<TagHash> {
+        self.get_all_by_type(etype, esubtype)
+            .into_iter()
+            .map(|(tag, _)| tag)
+            .collect()
     }
 }
 
@@ -17,14 +17,30 @@ pub enum Error {
     #[error("Padding bytes are not zero! Got {0:X?}")]
     PaddingNotZero(Vec<u8>),
 
-    #[error("String too long")]
-    StringTooLong,
+    #[error("String at 0x{offset:X} is longer than the maximum length of {max}")]
+    StringTooLong { max: usize, offset: u64 },
 
     #[error("Pointer is null")]
     PointerNull,
 
-    #[error("Enum variant {0} is out of range")]
-    EnumVariantOutOfRange(usize),
+    #[error("Enum variant {value} is out of range for {typename} ({repr}) at 0x{offset:X}, expected one of [{}]", .valid.join(", "))]
+    EnumVariantOutOfRange {
+        typename: String,
+        /// Raw value, formatted as the enum's repr type
+        value: String,
+        repr: &'static str,
+        /// Discriminants of all known variants
+        valid: Vec<String>,
+        offset: u64,
+    },
+
+    #[error("Unknown bits {bits} set in flags {typename} at 0x{offset:X}")]
+    UnknownFlagBits {
+        typename: String,
+        /// Bits that don't correspond to any known flag
+        bits: String,
+        offset: u64,
+    },
 
     #[error("Unknown variant class 0x{class:X} for variant enum {typename}")]
     MissingVariantType { class: u32, typename: String },
@@ -41,6 +57,28 @@ pub enum Error {
     TagReadFailed(String),
 }
 
+impl Error {
+    /// Name of the error variant, looking through propagated errors
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::Io(_) => "Io",
+            Error::PropagatedError { error, .. } => error.kind(),
+            Error::TypeMismatch(_) => "TypeMismatch",
+            Error::PaddingNotZero(_) => "PaddingNotZero",
+            Error::StringTooLong { .. } => "StringTooLong",
+            Error::PointerNull => "PointerNull",
+            Error::EnumVariantOutOfRange { .. } => "EnumVariantOutOfRange",
+            Error::UnknownFlagBits { .. } => "UnknownFlagBits",
+            Error::MissingVariantType { .. } => "MissingVariantType",
+            Error::InvalidStructure(_) => "InvalidStructure",
+            #[cfg(feature = "tiger_pkg")]
+            Error::Hash64LookupFailed(_) => "Hash64LookupFailed",
+            #[cfg(feature = "tiger_pkg")]
+            Error::TagReadFailed(_) => "TagReadFailed",
+        }
+    }
+}
+
 /// Represents a field in a propagated error, eg. `User.name`
 #[derive(Debug)]
 pub enum FieldRecord {
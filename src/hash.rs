@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use crate::{
+    string::{DisplayFn, StringContainer},
+    Endian, FnvHash, TigerReadable, TigerReader,
+};
+
+const FNV1A32_OFFSET_BASIS: u32 = 0x811C9DC5;
+const FNV1A32_PRIME: u32 = 0x01000193;
+
+/// Hashes `bytes` with 32-bit FNV-1a.
+///
+/// This is a const fn, so hashed names can be used in constants and `#[tiger(..)]` attributes.
+pub const fn fnv1a32(bytes: &[u8]) -> FnvHash {
+    Fnv1a32::new().update(bytes).finish()
+}
+
+/// Incremental 32-bit FNV-1a hasher
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fnv1a32(u32);
+
+impl Fnv1a32 {
+    pub const fn new() -> Self {
+        Self(FNV1A32_OFFSET_BASIS)
+    }
+
+    pub const fn update(mut self, bytes: &[u8]) -> Self {
+        let mut i = 0;
+        while i < bytes.len() {
+            self.0 ^= bytes[i] as u32;
+            self.0 = self.0.wrapping_mul(FNV1A32_PRIME);
+            i += 1;
+        }
+
+        self
+    }
+
+    pub const fn finish(&self) -> FnvHash {
+        self.0
+    }
+}
+
+impl Default for Fnv1a32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::hash::Hasher for Fnv1a32 {
+    fn finish(&self) -> u64 {
+        self.0 as u64
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        *self = self.update(bytes);
+    }
+}
+
+/// FNV-1a hash of a name, such as a class or field name.
+///
+/// The name itself isn't stored, use [`Self::resolve`] with a [`NameDictionary`] to get it back.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HashedName(pub FnvHash);
+
+impl HashedName {
+    pub const fn new(name: &str) -> Self {
+        Self(fnv1a32(name.as_bytes()))
+    }
+
+    pub fn resolve<'a>(&self, dictionary: &'a (impl StringContainer + ?Sized)) -> Option<&'a str> {
+        dictionary.get_string(self.0)
+    }
+
+    /// Displays the resolved name, or the hash if it is not in `dictionary`
+    pub fn display<'a>(
+        &self,
+        dictionary: &'a (impl StringContainer + ?Sized),
+    ) -> impl std::fmt::Display + 'a {
+        let hash = *self;
+        let resolved = self.resolve(dictionary);
+        DisplayFn(move |f: &mut std::fmt::Formatter<'_>| match resolved {
+            Some(name) => f.write_str(name),
+            None => std::fmt::Display::fmt(&hash, f),
+        })
+    }
+}
+
+impl TigerReadable for HashedName {
+    fn read_ds_endian(reader: &mut dyn TigerReader, endian: Endian) -> crate::Result<Self> {
+        Ok(Self(FnvHash::read_ds_endian(reader, endian)?))
+    }
+
+    const ID: Option<u32> = None;
+    const SIZE: usize = std::mem::size_of::<FnvHash>();
+}
+
+impl From<&str> for HashedName {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl std::fmt::Debug for HashedName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HashedName({self})")
+    }
+}
+
+/// Displays the hash, as the name needs a dictionary to be resolved
+impl std::fmt::Display for HashedName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "name:{:08X}", self.0)
+    }
+}
+
+/// Known names, keyed by their FNV-1a hash
+#[derive(Debug, Clone, Default)]
+pub struct NameDictionary {
+    names: HashMap<FnvHash, String>,
+}
+
+impl NameDictionary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `name` to the dictionary, returning its hash
+    pub fn insert(&mut self, name: impl Into<String>) -> HashedName {
+        let name = name.into();
+        let hash = HashedName::new(&name);
+        self.names.insert(hash.0, name);
+        hash
+    }
+
+    pub fn get(&self, hash: HashedName) -> Option<&str> {
+        self.get_string(hash.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+impl StringContainer for NameDictionary {
+    fn get_string(&self, hash: FnvHash) -> Option<&str> {
+        self.names.get(&hash).map(String::as_str)
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for NameDictionary {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut dictionary = Self::new();
+        dictionary.extend(iter);
+        dictionary
+    }
+}
+
+impl<S: Into<String>> Extend<S> for NameDictionary {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for name in iter {
+            self.insert(name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{fnv1a32, HashedName, NameDictionary};
+    use crate::TigerReadable;
+
+    #[test]
+    fn test_hashed_name() -> crate::Result<()> {
+        assert_eq!(fnv1a32(b""), 0x811C9DC5);
+        assert_eq!(fnv1a32(b"a"), 0xE40C292C);
+        assert_eq!(fnv1a32(b"foobar"), 0xBF9CF968);
+
+        const NAME: HashedName = HashedName::new("foobar");
+        let dictionary: NameDictionary = ["foobar", "a"].into_iter().collect();
+        let name = HashedName::read_ds(&mut Cursor::new(0xBF9CF968u32.to_le_bytes()))?;
+        assert_eq!(name, NAME);
+        assert_eq!(name.resolve(&dictionary), Some("foobar"));
+        assert_eq!(
+            HashedName(1).display(&dictionary).to_string(),
+            "name:00000001"
+        );
+
+        Ok(())
+    }
+}
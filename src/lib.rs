@@ -1,3 +1,5 @@
+// Re-exported so TigerFlags types don't need a direct bitflags dependency
+pub use bitflags;
 // Re-export to shorten typenames in struct backtraces
 #[doc(hidden)]
 pub use disqualified::ShortName;
@@ -6,26 +8,41 @@ pub use disqualified::ShortName;
 pub use linkme::distributed_slice;
 #[doc(hidden)]
 pub use paste::paste;
+#[doc(hidden)]
+#[cfg(feature = "tiger_pkg")]
+pub use tiger_pkg;
+#[doc(hidden)]
+#[cfg(feature = "tracing")]
+pub use tracing;
 
 pub type Result<T> = std::result::Result<T, error::Error>;
 
 pub use error::{Error, ResultExt};
-pub use pointer::{Pointer, PointerOptional, ResourcePointer};
+pub use pointer::{
+    LazyResourcePointer, Pointer, PointerOptional, ResourcePointer, TypedResourcePointer,
+    TypedResourcePointerOptional,
+};
 
 #[cfg(feature = "tiger_pkg")]
 pub use pointer::ResourcePointerWithClass;
 
+pub use hash::{fnv1a32, Fnv1a32, HashedName, NameDictionary};
 pub use padding::Padding;
-pub use string::NullString;
-pub use tiger_parse_derive::{tiger_type, TigerFlags};
-pub use variant::{OptionalVariantPointer, VariantEnum, VariantPointer};
+pub use string::{
+    BoundedNullString, FixedString, FixedWideString, LocalizedString, NullString, StringContainer,
+    StringPointer, WideString,
+};
+pub use tiger_parse_derive::{tiger_type, tiger_variant, TigerFlags};
+pub use variant::{
+    OptionalVariantPointer, VariantDispatch, VariantEnum, VariantHeader, VariantPointer,
+};
+pub use vector::{read_inline_vec, InlineVec};
 
 pub type FnvHash = u32;
 
 mod array;
 mod padding;
 mod tuples;
-mod variant;
 mod vector;
 
 mod glam_support;
@@ -33,15 +50,47 @@ mod glam_support;
 #[cfg(feature = "reflect")]
 pub mod reflect;
 
+#[cfg(feature = "reflect")]
+pub mod byte_diff;
+
+#[cfg(feature = "diff")]
+pub mod diff;
+
 pub mod error;
+pub mod hash;
 pub mod pointer;
 pub mod string;
+pub mod variant;
+
+#[cfg(feature = "visit")]
+pub mod visit;
 
 #[cfg(feature = "tiger_pkg")]
 pub mod dpkg;
 
 #[cfg(feature = "tiger_pkg")]
-pub use dpkg::PackageManagerExt;
+pub mod bulk;
+
+#[cfg(feature = "tiger_pkg")]
+pub mod cache;
+
+#[cfg(feature = "tiger_pkg")]
+pub mod source;
+
+#[cfg(feature = "tiger_pkg")]
+pub mod tag;
+
+#[cfg(feature = "tiger_pkg")]
+pub use cache::TagCache;
+
+#[cfg(feature = "tiger_pkg")]
+pub use dpkg::{DataTagHeader, PackageManagerExt};
+
+#[cfg(feature = "tiger_pkg")]
+pub use source::TagSource;
+
+#[cfg(feature = "tiger_pkg")]
+pub use tag::{Tag, Tag64};
 
 use std::io::{Read, Seek};
 
@@ -57,7 +106,7 @@ type Offset = i64;
 #[cfg(not(feature = "32bit"))]
 type Size = i64;
 
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Endian {
     Little,
     Big,
@@ -78,10 +127,21 @@ pub trait TigerReadable: Sized {
     /// 0x8080XXXX structure ID
     const ID: Option<u32> = None;
 
+    /// Entry type and optional subtype of the tag
     const ETYPE: Option<(u8, Option<u8>)> = None;
 
+    /// All accepted entry types and optional subtypes, defaults to [`Self::ETYPE`]
+    const ETYPES: &'static [(u8, Option<u8>)] = match Self::ETYPE {
+        Some(etype) => &[etype],
+        None => &[],
+    };
+
     /// Total size of this struct, in bytes
     const SIZE: usize;
+
+    /// Whether the size of this type depends on its data, such as structs with inline counted arrays.
+    /// [`Self::SIZE`] then only covers the fixed part, so these types can't be used as elements of arrays or [`Vec`]s.
+    const VARIABLE_SIZE: bool = false;
 }
 
 macro_rules! impl_read_primitives {
@@ -145,6 +205,23 @@ where
     }
 
     const SIZE: usize = T::SIZE;
+    const VARIABLE_SIZE: bool = T::VARIABLE_SIZE;
+}
+
+/// Reads a raw value and converts it to the field type, used by `#[tiger(map = "...")]`
+#[doc(hidden)]
+pub fn read_mapped<R: TigerReadable, O>(
+    reader: &mut dyn TigerReader,
+    endian: Endian,
+    map: fn(R) -> O,
+) -> Result<O> {
+    Ok(map(R::read_ds_endian(reader, endian)?))
+}
+
+/// Size of the raw value read for `#[tiger(map = "...")]`
+#[doc(hidden)]
+pub const fn mapped_size<R: TigerReadable, O>(_map: fn(R) -> O) -> usize {
+    R::SIZE
 }
 
 #[cfg(feature = "reflect")]
@@ -153,6 +230,9 @@ macro_rules! reflection_container {
     () => {
         #[$crate::distributed_slice]
         static STRUCTS: [$crate::reflect::ReflectedStruct];
+
+        #[$crate::distributed_slice]
+        static FLAGS: [$crate::reflect::ReflectedFlags];
     };
 }
 
@@ -161,3 +241,25 @@ macro_rules! reflection_container {
 macro_rules! reflection_container {
     () => {};
 }
+
+/// Registers concrete instantiations of generic `tiger_type` structs in the reflection container,
+/// eg. `reflect_instance!(Pair<u32>, Pair<f32>)`
+#[cfg(feature = "reflect")]
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)] // The container lives in the calling crate
+macro_rules! reflect_instance {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            const _: () = {
+                #[$crate::distributed_slice(crate::STRUCTS)]
+                static REFLECT: $crate::reflect::ReflectedStruct = <$ty>::__tiger_reflect(stringify!($ty));
+            };
+        )+
+    };
+}
+
+#[cfg(not(feature = "reflect"))]
+#[macro_export]
+macro_rules! reflect_instance {
+    ($($ty:ty),+ $(,)?) => {};
+}
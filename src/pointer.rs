@@ -1,6 +1,7 @@
 use std::{
     fmt::{Debug, Formatter},
     io::SeekFrom,
+    marker::PhantomData,
     ops::Deref,
 };
 
@@ -10,6 +11,7 @@ pub struct Pointer<T: TigerReadable>(pub T, Offset);
 
 impl<T: TigerReadable> TigerReadable for Pointer<T> {
     fn read_ds_endian(reader: &mut dyn TigerReader, endian: crate::Endian) -> crate::Result<Self> {
+        #[allow(clippy::unnecessary_cast)] // Offset is i32 with the 32bit feature
         let ptr = reader.stream_position()? as i64 + Offset::read_ds_endian(reader, endian)? as i64;
         let save_pos = reader.stream_position()?;
 
@@ -65,6 +67,7 @@ impl<T: TigerReadable> TigerReadable for PointerOptional<T> {
             return Ok(PointerOptional(None, ptr_pos as Offset));
         }
 
+        #[allow(clippy::unnecessary_cast)]
         let ptr = ptr_pos + ptr_data as i64;
         let save_pos = reader.stream_position()?;
 
@@ -157,6 +160,196 @@ impl Debug for ResourcePointer {
     }
 }
 
+/// Checks the type of a resource against `T::ID`, following the same rules as `Vec<T>` element types
+#[allow(unused_variables, clippy::extra_unused_type_parameters)] // Unused without check_types
+fn check_resource_type<T: TigerReadable>(resource_type: u32, offset: u64) -> crate::Result<()> {
+    #[cfg(feature = "check_types")]
+    if T::ID.is_some()
+        && (T::ID != Some(u32::MAX) || cfg!(feature = "check_types_strict"))
+        && resource_type != T::ID.unwrap()
+    {
+        return Err(crate::Error::TypeMismatch(format!(
+            "Resource type mismatch! Expected 0x{:08X}, got 0x{resource_type:08X} (resource @ 0x{offset:X})",
+            T::ID.unwrap(),
+        )));
+    }
+
+    #[cfg(feature = "check_types_debug")]
+    if T::ID == Some(u32::MAX) {
+        tracing::warn!(
+            "Rust tag has no ID, please set one. Resource type ID is 0x{resource_type:08X} for Rust type {} (0x{:08X})",
+            std::any::type_name::<T>(), T::ID.unwrap_or(u32::MAX)
+        );
+    }
+
+    Ok(())
+}
+
+/// Resource pointer that eagerly reads its target as `T`.
+///
+/// The resource type is checked against `T::ID`, and null pointers return [`Error::PointerNull`](crate::Error::PointerNull),
+/// use [`TypedResourcePointerOptional`] for pointers that can be null.
+pub struct TypedResourcePointer<T: TigerReadable>(pub T, ResourcePointer);
+
+impl<T: TigerReadable> TigerReadable for TypedResourcePointer<T> {
+    fn read_ds_endian(reader: &mut dyn TigerReader, endian: crate::Endian) -> crate::Result<Self> {
+        let pointer = LazyResourcePointer::<T>::read_ds_endian(reader, endian)?;
+        let data = pointer.read(reader, endian)?;
+
+        Ok(TypedResourcePointer(data, pointer.pointer))
+    }
+
+    const ID: Option<u32> = None;
+    const SIZE: usize = std::mem::size_of::<Offset>();
+}
+
+impl<T: TigerReadable> TypedResourcePointer<T> {
+    pub fn offset(&self) -> u64 {
+        self.1.offset
+    }
+
+    pub fn resource_type(&self) -> u32 {
+        self.1.resource_type
+    }
+}
+
+impl<T: TigerReadable> Deref for TypedResourcePointer<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: TigerReadable + Debug> Debug for TypedResourcePointer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TypedResourcePointer")
+            .field(&self.0)
+            .field(&self.1)
+            .finish()
+    }
+}
+
+impl<T: TigerReadable + Clone> Clone for TypedResourcePointer<T> {
+    fn clone(&self) -> Self {
+        TypedResourcePointer(self.0.clone(), self.1)
+    }
+}
+
+/// [`TypedResourcePointer`] that reads null pointers as `None`
+pub struct TypedResourcePointerOptional<T: TigerReadable>(pub Option<T>, ResourcePointer);
+
+impl<T: TigerReadable> TigerReadable for TypedResourcePointerOptional<T> {
+    fn read_ds_endian(reader: &mut dyn TigerReader, endian: crate::Endian) -> crate::Result<Self> {
+        let pointer = LazyResourcePointer::<T>::read_ds_endian(reader, endian)?;
+        if !pointer.is_valid() {
+            return Ok(TypedResourcePointerOptional(None, pointer.pointer));
+        }
+
+        let data = pointer.read(reader, endian)?;
+        Ok(TypedResourcePointerOptional(Some(data), pointer.pointer))
+    }
+
+    const ID: Option<u32> = None;
+    const SIZE: usize = std::mem::size_of::<Offset>();
+}
+
+impl<T: TigerReadable> TypedResourcePointerOptional<T> {
+    pub fn offset(&self) -> Option<u64> {
+        self.1.is_valid.then_some(self.1.offset)
+    }
+
+    pub fn resource_type(&self) -> Option<u32> {
+        self.1.is_valid.then_some(self.1.resource_type)
+    }
+}
+
+impl<T: TigerReadable> Deref for TypedResourcePointerOptional<T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: TigerReadable + Debug> Debug for TypedResourcePointerOptional<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TypedResourcePointerOptional")
+            .field(&self.0)
+            .field(&self.1)
+            .finish()
+    }
+}
+
+impl<T: TigerReadable + Clone> Clone for TypedResourcePointerOptional<T> {
+    fn clone(&self) -> Self {
+        TypedResourcePointerOptional(self.0.clone(), self.1)
+    }
+}
+
+/// Resource pointer that reads its target as `T` on demand.
+///
+/// The resource type is checked against `T::ID` when the pointer itself is read.
+pub struct LazyResourcePointer<T: TigerReadable> {
+    pub pointer: ResourcePointer,
+    _marker: PhantomData<T>,
+}
+
+impl<T: TigerReadable> TigerReadable for LazyResourcePointer<T> {
+    fn read_ds_endian(reader: &mut dyn TigerReader, endian: crate::Endian) -> crate::Result<Self> {
+        let pointer = ResourcePointer::read_ds_endian(reader, endian)?;
+        if pointer.is_valid {
+            check_resource_type::<T>(pointer.resource_type, pointer.offset)?;
+        }
+
+        Ok(LazyResourcePointer {
+            pointer,
+            _marker: PhantomData,
+        })
+    }
+
+    const ID: Option<u32> = None;
+    const SIZE: usize = std::mem::size_of::<Offset>();
+}
+
+impl<T: TigerReadable> LazyResourcePointer<T> {
+    pub fn is_valid(&self) -> bool {
+        self.pointer.is_valid
+    }
+
+    /// Reads the target of the pointer from the stream it was read from, restoring the stream position afterwards
+    pub fn read(&self, reader: &mut dyn TigerReader, endian: crate::Endian) -> crate::Result<T> {
+        if !self.pointer.is_valid {
+            return Err(crate::Error::PointerNull);
+        }
+
+        let save_pos = reader.stream_position()?;
+        reader.seek(SeekFrom::Start(self.pointer.offset))?;
+        let data = T::read_ds_endian(reader, endian);
+        reader.seek(SeekFrom::Start(save_pos))?;
+
+        data
+    }
+}
+
+impl<T: TigerReadable> Debug for LazyResourcePointer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "LazyResourcePointer<{}>(type=0x{:08x})",
+            crate::ShortName::of::<T>(),
+            self.pointer.resource_type
+        ))
+    }
+}
+
+impl<T: TigerReadable> Clone for LazyResourcePointer<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: TigerReadable> Copy for LazyResourcePointer<T> {}
+
 #[cfg(feature = "tiger_pkg")]
 #[derive(Clone, Copy)]
 pub struct ResourcePointerWithClass {
@@ -221,10 +414,14 @@ impl Debug for ResourcePointerWithClass {
 }
 
 #[cfg(test)]
+#[allow(clippy::unusual_byte_groupings)] // Test values are grouped as words
 mod tests {
     use std::io::{Cursor, Seek};
 
-    use crate::{Pointer, TigerReadable};
+    use crate::{
+        LazyResourcePointer, Offset, Pointer, TigerReadable, TypedResourcePointer,
+        TypedResourcePointerOptional,
+    };
 
     #[test]
     fn test_pointer() {
@@ -266,4 +463,65 @@ mod tests {
         println!("{:X}", *ptr);
         assert_eq!(*ptr, 0xfeed_da_beef)
     }
+
+    #[derive(Debug)]
+    struct Resource(u32);
+
+    impl TigerReadable for Resource {
+        fn read_ds_endian(
+            reader: &mut dyn crate::TigerReader,
+            endian: crate::Endian,
+        ) -> crate::Result<Self> {
+            Ok(Resource(u32::read_ds_endian(reader, endian)?))
+        }
+
+        const ID: Option<u32> = Some(0x80801234);
+        const SIZE: usize = 4;
+    }
+
+    #[test]
+    fn test_typed_resource_pointer() {
+        // Pointer, resource type and the resource itself
+        let mut data = vec![0u8; 0x14];
+        let pointer = (0x10 as Offset).to_le_bytes();
+        data[..pointer.len()].copy_from_slice(&pointer);
+        data[0xC..0x10].copy_from_slice(&0x80801234u32.to_le_bytes());
+        data[0x10..0x14].copy_from_slice(&123u32.to_le_bytes());
+
+        let mut cursor = Cursor::new(&data);
+        let ptr: TypedResourcePointer<Resource> =
+            TigerReadable::read_ds_endian(&mut cursor, crate::Endian::Little).unwrap();
+        assert_eq!(ptr.0 .0, 123);
+        assert_eq!((ptr.offset(), ptr.resource_type()), (0x10, 0x80801234));
+        assert_eq!(cursor.position(), pointer.len() as u64);
+
+        cursor.set_position(0);
+        let ptr: LazyResourcePointer<Resource> =
+            TigerReadable::read_ds_endian(&mut cursor, crate::Endian::Little).unwrap();
+        assert_eq!(ptr.read(&mut cursor, crate::Endian::Little).unwrap().0, 123);
+        assert_eq!(cursor.position(), pointer.len() as u64);
+
+        let ptr: TypedResourcePointerOptional<Resource> =
+            TigerReadable::read_ds_endian(&mut Cursor::new(&data), crate::Endian::Little).unwrap();
+        assert_eq!(ptr.as_ref().map(|r| r.0), Some(123));
+        assert_eq!(ptr.offset(), Some(0x10));
+
+        #[cfg(feature = "check_types")]
+        {
+            data[0xC..0x10].copy_from_slice(&0x80805678u32.to_le_bytes());
+            let result: crate::Result<TypedResourcePointer<Resource>> =
+                TigerReadable::read_ds_endian(&mut Cursor::new(&data), crate::Endian::Little);
+            assert!(matches!(result, Err(crate::Error::TypeMismatch(_))));
+        }
+
+        data[..pointer.len()].fill(0);
+        let result: crate::Result<TypedResourcePointer<Resource>> =
+            TigerReadable::read_ds_endian(&mut Cursor::new(&data), crate::Endian::Little);
+        assert!(matches!(result, Err(crate::Error::PointerNull)));
+
+        let ptr: TypedResourcePointerOptional<Resource> =
+            TigerReadable::read_ds_endian(&mut Cursor::new(&data), crate::Endian::Little).unwrap();
+        assert!(ptr.is_none());
+        assert_eq!((ptr.offset(), ptr.resource_type()), (None, None));
+    }
 }
@@ -29,12 +29,48 @@ impl std::fmt::Display for ReflectedStruct {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ReflectedFlags {
+    pub name: Cow<'static, str>,
+    /// Size of the underlying bits type, in bytes
+    pub size: usize,
+    pub flags: Cow<'static, [ReflectedFlag]>,
+}
+
+impl std::fmt::Display for ReflectedFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("struct {}: u{} {{", self.name, self.size * 8))?;
+        for flag in self.flags.iter() {
+            f.write_fmt(format_args!(
+                "\n    const {} = 0x{:X};",
+                flag.name, flag.value
+            ))?;
+        }
+        f.write_str("\n}")
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReflectedFlag {
+    pub name: &'static str,
+    pub value: u128,
+}
+
 #[derive(Debug, Clone)]
 pub struct ReflectedField {
     pub name: Cow<'static, str>,
     pub size: usize,
     pub offset: usize,
     pub explicit_offset: bool,
+    /// Condition expression for fields declared with `#[tiger(if = "...")]`
+    pub condition: Option<Cow<'static, str>>,
+    /// Whether an absent conditional field still takes up space, see `#[tiger(if = "...", reserve)]`
+    pub reserve: bool,
+    /// Count expression for inline arrays declared with `#[tiger(count = "...")]`
+    pub count: Option<Cow<'static, str>>,
+    /// Whether the size of the field depends on the data, see `TigerReadable::VARIABLE_SIZE`.
+    /// Later fields only have a static offset again once one is declared explicitly.
+    pub variable_size: bool,
     pub ty: ReflectedType,
 }
 
@@ -43,6 +79,17 @@ impl std::fmt::Display for ReflectedField {
         if self.explicit_offset {
             f.write_fmt(format_args!("#[tiger(offset = 0x{:X})] ", self.offset))?;
         }
+        if let Some(count) = &self.count {
+            f.write_fmt(format_args!("#[tiger(count = {count:?})] "))?;
+        }
+        if let Some(condition) = &self.condition {
+            if self.reserve {
+                f.write_fmt(format_args!("#[tiger(if = {condition:?}, reserve)] "))?;
+            } else {
+                f.write_fmt(format_args!("#[tiger(if = {condition:?})] "))?;
+            }
+            return f.write_fmt(format_args!("{}: Option<{}>", self.name, self.ty));
+        }
         f.write_fmt(format_args!("{}: {}", self.name, self.ty))
     }
 }
@@ -65,11 +112,19 @@ pub enum ReflectedType {
     Vec4,
 
     TagHash,
+    /// Relative pointer to a null-terminated string
+    StringPointer,
+    /// Hash of a string in a localized string container
+    LocalizedString,
+    /// FNV-1a hash of a name
+    HashedName,
     // Struct(u32),
     Padding(usize),
 
     Tuple(Cow<'static, [ReflectedType]>),
     Array(CowBox<'static, ReflectedType>),
+    Pointer(CowBox<'static, ReflectedType>),
+    PointerOptional(CowBox<'static, ReflectedType>),
     FixedArray(usize, CowBox<'static, ReflectedType>),
     Other(Cow<'static, str>),
 }
@@ -91,6 +146,9 @@ impl std::fmt::Display for ReflectedType {
             ReflectedType::Vec3 => f.write_str("Vec3"),
             ReflectedType::Vec4 => f.write_str("Vec4"),
             ReflectedType::TagHash => f.write_str("TagHash"),
+            ReflectedType::StringPointer => f.write_str("StringPointer"),
+            ReflectedType::LocalizedString => f.write_str("LocalizedString"),
+            ReflectedType::HashedName => f.write_str("HashedName"),
             ReflectedType::Padding(size) => f.write_fmt(format_args!("Padding<{size}>")),
             ReflectedType::Tuple(fields) => f.write_fmt(format_args!(
                 "({})",
@@ -101,6 +159,12 @@ impl std::fmt::Display for ReflectedType {
                     .join(", ")
             )),
             ReflectedType::Array(inner_type) => f.write_fmt(format_args!("Vec<{inner_type}>")),
+            ReflectedType::Pointer(inner_type) => {
+                f.write_fmt(format_args!("Pointer<{inner_type}>"))
+            }
+            ReflectedType::PointerOptional(inner_type) => {
+                f.write_fmt(format_args!("PointerOptional<{inner_type}>"))
+            }
             ReflectedType::FixedArray(size, inner_type) => {
                 f.write_fmt(format_args!("[{inner_type}; {size}]"))
             }
@@ -109,6 +173,96 @@ impl std::fmt::Display for ReflectedType {
     }
 }
 
+/// Reflected type of a concrete type.
+///
+/// Used to resolve the fields of generic structs for each instantiation registered with
+/// [`reflect_instance!`](crate::reflect_instance), `tiger_type` structs, enums and flags implement it automatically.
+pub trait ReflectType {
+    const REFLECTED_TYPE: ReflectedType;
+}
+
+macro_rules! impl_reflect_type {
+    ($($ty:ty => $reflected:expr),+ $(,)?) => {
+        $(
+            impl ReflectType for $ty {
+                const REFLECTED_TYPE: ReflectedType = $reflected;
+            }
+        )+
+    };
+}
+
+impl_reflect_type! {
+    u8 => ReflectedType::UInt8,
+    u16 => ReflectedType::UInt16,
+    u32 => ReflectedType::UInt32,
+    u64 => ReflectedType::UInt64,
+    i8 => ReflectedType::Int8,
+    i16 => ReflectedType::Int16,
+    i32 => ReflectedType::Int32,
+    i64 => ReflectedType::Int64,
+    f32 => ReflectedType::Float32,
+    f64 => ReflectedType::Float64,
+    bool => ReflectedType::Other(Cow::Borrowed("bool")),
+    glam::Vec2 => ReflectedType::Vec2,
+    glam::Vec3 => ReflectedType::Vec3,
+    glam::Vec4 => ReflectedType::Vec4,
+    crate::StringPointer => ReflectedType::StringPointer,
+    crate::LocalizedString => ReflectedType::LocalizedString,
+    crate::HashedName => ReflectedType::HashedName,
+    crate::NullString => ReflectedType::Other(Cow::Borrowed("NullString")),
+    crate::WideString => ReflectedType::Other(Cow::Borrowed("WideString")),
+    crate::ResourcePointer => ReflectedType::Other(Cow::Borrowed("ResourcePointer")),
+}
+
+#[cfg(feature = "tiger_pkg")]
+impl_reflect_type!(tiger_pkg::TagHash => ReflectedType::TagHash);
+
+impl<const N: usize> ReflectType for crate::Padding<N> {
+    const REFLECTED_TYPE: ReflectedType = ReflectedType::Padding(N);
+}
+
+impl<T: ReflectType> ReflectType for Box<T> {
+    const REFLECTED_TYPE: ReflectedType = T::REFLECTED_TYPE;
+}
+
+impl<T: ReflectType> ReflectType for Vec<T> {
+    const REFLECTED_TYPE: ReflectedType =
+        ReflectedType::Array(CowBox::Borrowed(&T::REFLECTED_TYPE));
+}
+
+impl<T: ReflectType, const N: usize> ReflectType for [T; N] {
+    const REFLECTED_TYPE: ReflectedType =
+        ReflectedType::FixedArray(N, CowBox::Borrowed(&T::REFLECTED_TYPE));
+}
+
+impl<T: ReflectType + crate::TigerReadable> ReflectType for crate::Pointer<T> {
+    const REFLECTED_TYPE: ReflectedType =
+        ReflectedType::Pointer(CowBox::Borrowed(&T::REFLECTED_TYPE));
+}
+
+impl<T: ReflectType + crate::TigerReadable> ReflectType for crate::PointerOptional<T> {
+    const REFLECTED_TYPE: ReflectedType =
+        ReflectedType::PointerOptional(CowBox::Borrowed(&T::REFLECTED_TYPE));
+}
+
+macro_rules! impl_reflect_type_tuple {
+    ($($name:ident)+) => {
+        impl<$($name: ReflectType),+> ReflectType for ($($name,)+) {
+            const REFLECTED_TYPE: ReflectedType =
+                ReflectedType::Tuple(Cow::Borrowed(&[$($name::REFLECTED_TYPE),+]));
+        }
+    };
+}
+
+impl_reflect_type_tuple! { A }
+impl_reflect_type_tuple! { A B }
+impl_reflect_type_tuple! { A B C }
+impl_reflect_type_tuple! { A B C D }
+impl_reflect_type_tuple! { A B C D E }
+impl_reflect_type_tuple! { A B C D E F }
+impl_reflect_type_tuple! { A B C D E F G }
+impl_reflect_type_tuple! { A B C D E F G H }
+
 /// Borrowed value or owned Box
 pub enum CowBox<'a, B: ?Sized + 'a>
 where
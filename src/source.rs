@@ -0,0 +1,405 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use tiger_pkg::{TagHash, TagHash64};
+
+use crate::{error::Error, Endian};
+
+/// Type information of a tag entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TagEntry {
+    pub reference: u32,
+    pub file_type: u8,
+    pub file_subtype: u8,
+}
+
+/// Source of raw tag data, such as a [`tiger_pkg::PackageManager`].
+///
+/// Every tag source implements [`PackageManagerExt`](crate::PackageManagerExt).
+pub trait TagSource: Send + Sync {
+    /// Returns the type information of a tag, if known.
+    ///
+    /// Type checks are skipped for tags without an entry.
+    fn entry(&self, tag: TagHash) -> Option<TagEntry>;
+
+    fn read_tag_data(&self, tag: TagHash) -> crate::Result<Vec<u8>>;
+
+    fn endian(&self) -> Endian;
+
+    /// Resolves a 64-bit tag hash to its 32-bit tag
+    fn resolve_tag64(&self, hash: TagHash64) -> Option<TagHash>;
+
+    /// Returns the reference (class ID) recorded for a 64-bit tag hash, if the source keeps one
+    fn tag64_reference(&self, hash: TagHash64) -> Option<u32> {
+        let _ = hash;
+        None
+    }
+
+    /// Looks up a named tag with the given class
+    fn named_tag(&self, name: &str, class: u32) -> Option<TagHash>;
+
+    /// Returns all tags in this source
+    fn tags(&self) -> Vec<TagHash>;
+
+    /// Returns all tags with the given reference (class ID)
+    fn tags_by_reference(&self, reference: u32) -> Vec<TagHash> {
+        self.tags()
+            .into_iter()
+            .filter(|&tag| self.entry(tag).is_some_and(|e| e.reference == reference))
+            .collect()
+    }
+
+    /// Returns all tags with the given type, and subtype if specified
+    fn tags_by_type(&self, etype: u8, esubtype: Option<u8>) -> Vec<TagHash> {
+        self.tags()
+            .into_iter()
+            .filter(|&tag| {
+                self.entry(tag).is_some_and(|e| {
+                    e.file_type == etype && esubtype.is_none_or(|s| s == e.file_subtype)
+                })
+            })
+            .collect()
+    }
+}
+
+/// Lookup tables shared by the tag sources in this module
+#[derive(Debug, Default, Clone)]
+struct TagLookup {
+    tag64: HashMap<TagHash64, TagHash>,
+    named: HashMap<(String, u32), TagHash>,
+}
+
+/// Tag source backed by in-memory buffers, mainly useful for testing
+#[derive(Debug, Clone)]
+pub struct MemoryTagSource {
+    endian: Endian,
+    tags: HashMap<TagHash, (TagEntry, Vec<u8>)>,
+    lookup: TagLookup,
+}
+
+impl MemoryTagSource {
+    pub fn new(endian: Endian) -> Self {
+        Self {
+            endian,
+            tags: HashMap::new(),
+            lookup: TagLookup::default(),
+        }
+    }
+
+    pub fn insert(&mut self, tag: TagHash, entry: TagEntry, data: impl Into<Vec<u8>>) {
+        self.tags.insert(tag, (entry, data.into()));
+    }
+
+    pub fn insert_tag64(&mut self, hash: TagHash64, tag: TagHash) {
+        self.lookup.tag64.insert(hash, tag);
+    }
+
+    pub fn insert_named(&mut self, name: impl Into<String>, class: u32, tag: TagHash) {
+        self.lookup.named.insert((name.into(), class), tag);
+    }
+}
+
+impl TagSource for MemoryTagSource {
+    fn entry(&self, tag: TagHash) -> Option<TagEntry> {
+        self.tags.get(&tag).map(|(entry, _)| *entry)
+    }
+
+    fn read_tag_data(&self, tag: TagHash) -> crate::Result<Vec<u8>> {
+        self.tags
+            .get(&tag)
+            .map(|(_, data)| data.clone())
+            .ok_or_else(|| Error::TagReadFailed(format!("Tag {tag} not found")))
+    }
+
+    fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    fn resolve_tag64(&self, hash: TagHash64) -> Option<TagHash> {
+        self.lookup.tag64.get(&hash).copied()
+    }
+
+    fn named_tag(&self, name: &str, class: u32) -> Option<TagHash> {
+        self.lookup.named.get(&(name.to_string(), class)).copied()
+    }
+
+    fn tags(&self) -> Vec<TagHash> {
+        self.tags.keys().copied().collect()
+    }
+}
+
+/// Tag source backed by a directory of extracted tags, named after their hash (eg. `80801234.bin`).
+///
+/// Extracted tags carry no type information, so entries have to be registered with [`Self::insert_entry`] for type checks.
+#[derive(Debug, Clone)]
+pub struct DirectoryTagSource {
+    endian: Endian,
+    files: HashMap<TagHash, PathBuf>,
+    entries: HashMap<TagHash, TagEntry>,
+    lookup: TagLookup,
+}
+
+impl DirectoryTagSource {
+    /// Indexes all `<hash>.bin` files in `path`. Other files are ignored.
+    pub fn new(path: impl AsRef<Path>, endian: Endian) -> crate::Result<Self> {
+        let mut files = HashMap::new();
+        for entry in std::fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|e| e != "bin") {
+                continue;
+            }
+
+            let Some(hash) = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .filter(|s| s.len() == 8)
+                .and_then(|s| u32::from_str_radix(s, 16).ok())
+            else {
+                continue;
+            };
+
+            files.insert(TagHash(hash), path);
+        }
+
+        Ok(Self {
+            endian,
+            files,
+            entries: HashMap::new(),
+            lookup: TagLookup::default(),
+        })
+    }
+
+    pub fn insert_entry(&mut self, tag: TagHash, entry: TagEntry) {
+        self.entries.insert(tag, entry);
+    }
+
+    pub fn insert_tag64(&mut self, hash: TagHash64, tag: TagHash) {
+        self.lookup.tag64.insert(hash, tag);
+    }
+
+    pub fn insert_named(&mut self, name: impl Into<String>, class: u32, tag: TagHash) {
+        self.lookup.named.insert((name.into(), class), tag);
+    }
+}
+
+impl TagSource for DirectoryTagSource {
+    fn entry(&self, tag: TagHash) -> Option<TagEntry> {
+        self.entries.get(&tag).copied()
+    }
+
+    fn read_tag_data(&self, tag: TagHash) -> crate::Result<Vec<u8>> {
+        let path = self
+            .files
+            .get(&tag)
+            .ok_or_else(|| Error::TagReadFailed(format!("Tag {tag} not found")))?;
+
+        std::fs::read(path).map_err(|e| Error::TagReadFailed(format!("{}: {e}", path.display())))
+    }
+
+    fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    fn resolve_tag64(&self, hash: TagHash64) -> Option<TagHash> {
+        self.lookup.tag64.get(&hash).copied()
+    }
+
+    fn named_tag(&self, name: &str, class: u32) -> Option<TagHash> {
+        self.lookup.named.get(&(name.to_string(), class)).copied()
+    }
+
+    fn tags(&self) -> Vec<TagHash> {
+        self.files.keys().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tiger_pkg::{TagHash, TagHash64};
+
+    use super::{DirectoryTagSource, MemoryTagSource, TagEntry, TagSource};
+    use crate::{error::Error, Endian, PackageManagerExt, Tag, TigerReadable, TigerReader};
+
+    #[derive(Debug)]
+    struct TestTag {
+        value: u32,
+        child: Tag<TestTag>,
+    }
+
+    impl TigerReadable for TestTag {
+        fn read_ds_endian(reader: &mut dyn TigerReader, endian: Endian) -> crate::Result<Self> {
+            Ok(TestTag {
+                value: TigerReadable::read_ds_endian(reader, endian)?,
+                child: TigerReadable::read_ds_endian(reader, endian)?,
+            })
+        }
+
+        const ID: Option<u32> = Some(0x80801234);
+        const SIZE: usize = 8;
+    }
+
+    fn test_tag_data(value: u32, child: TagHash) -> Vec<u8> {
+        [value.to_le_bytes(), child.0.to_le_bytes()].concat()
+    }
+
+    const TEST_ENTRY: TagEntry = TagEntry {
+        reference: 0x80801234,
+        file_type: 8,
+        file_subtype: 0,
+    };
+
+    #[test]
+    fn test_memory_source() -> crate::Result<()> {
+        let root = TagHash::new(1, 0);
+        let child = TagHash::new(1, 1);
+        let other = TagHash::new(1, 2);
+
+        let mut source = MemoryTagSource::new(Endian::Little);
+        source.insert(root, TEST_ENTRY, test_tag_data(1, child));
+        source.insert(child, TEST_ENTRY, test_tag_data(2, TagHash::NONE));
+        source.insert(
+            other,
+            TagEntry {
+                reference: 0x80805678,
+                ..TEST_ENTRY
+            },
+            test_tag_data(3, TagHash::NONE),
+        );
+        source.insert_tag64(TagHash64(0x1234), root);
+        source.insert_named("root", 0x80801234, root);
+
+        let tag: TestTag = source.read_tag_struct(root)?;
+        assert_eq!(tag.value, 1);
+        assert_eq!(tag.child.load(&source)?.value, 2);
+        assert!(tag
+            .child
+            .load(&source)?
+            .child
+            .load_optional(&source)?
+            .is_none());
+
+        let tag: TestTag = source.read_tag64_struct(TagHash64(0x1234))?;
+        assert_eq!(tag.value, 1);
+        let tag: TestTag = source.read_named_tag_struct("root")?;
+        assert_eq!(tag.value, 1);
+
+        #[cfg(feature = "check_types")]
+        assert!(matches!(
+            source.read_tag_struct::<TestTag>(other),
+            Err(Error::TypeMismatch(_))
+        ));
+        assert!(matches!(
+            source.read_tag64_struct::<TestTag>(TagHash64(0x5678)),
+            Err(Error::Hash64LookupFailed(_))
+        ));
+
+        let all = source.read_all_tag_structs::<TestTag>()?;
+        assert_eq!(all.stats().succeeded, 2);
+
+        // Works through trait objects too
+        let source: &dyn TagSource = &source;
+        assert_eq!(source.read_tag_struct::<TestTag>(child)?.value, 2);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "check_types")]
+    #[test]
+    fn test_etype_check() -> crate::Result<()> {
+        struct TypedTag;
+
+        impl TigerReadable for TypedTag {
+            fn read_ds_endian(_: &mut dyn TigerReader, _: Endian) -> crate::Result<Self> {
+                Ok(TypedTag)
+            }
+
+            const ETYPES: &'static [(u8, Option<u8>)] = &[(8, Some(1)), (16, None)];
+            const SIZE: usize = 0;
+        }
+
+        let mut source = MemoryTagSource::new(Endian::Little);
+        for (i, (file_type, file_subtype)) in [(8, 1), (8, 2), (16, 5)].into_iter().enumerate() {
+            let entry = TagEntry {
+                reference: u32::MAX,
+                file_type,
+                file_subtype,
+            };
+            source.insert(TagHash::new(1, i as u16), entry, vec![]);
+        }
+
+        assert!(source
+            .read_tag_struct::<TypedTag>(TagHash::new(1, 0))
+            .is_ok());
+        assert!(matches!(
+            source.read_tag_struct::<TypedTag>(TagHash::new(1, 1)),
+            Err(Error::TypeMismatch(_))
+        ));
+        assert!(source
+            .read_tag_struct::<TypedTag>(TagHash::new(1, 2))
+            .is_ok());
+        assert_eq!(source.read_all_tag_structs::<TypedTag>()?.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_data_tag() -> crate::Result<()> {
+        let header = TagHash::new(1, 0);
+        let data = TagHash::new(1, 1);
+
+        let mut source = MemoryTagSource::new(Endian::Little);
+        let header_entry = TagEntry {
+            reference: data.0,
+            file_type: 32,
+            file_subtype: 1,
+        };
+        source.insert(header, header_entry, 0x10u32.to_le_bytes());
+        let data_entry = TagEntry {
+            reference: u32::MAX,
+            file_type: 48,
+            file_subtype: 0,
+        };
+        source.insert(data, data_entry, [1, 0, 2, 0, 3, 0]);
+
+        assert_eq!(source.data_tag_of(header)?, data);
+        let (size, elements): (u32, Vec<u16>) = source.read_tag_with_elements(header)?;
+        assert_eq!((size, elements), (0x10, vec![1, 2, 3]));
+
+        let (_, raw): (u32, Vec<u8>) = source.read_tag_with_data(header)?;
+        assert_eq!(raw.len(), 6);
+
+        assert!(matches!(
+            source.read_tag_with_elements::<u32, u32>(header),
+            Err(Error::InvalidStructure(_))
+        ));
+        assert!(source.data_tag_of(data).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_directory_source() -> crate::Result<()> {
+        let root = TagHash::new(1, 0);
+        let dir = std::env::temp_dir().join(format!("tiger-parse-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(
+            dir.join(format!("{:08X}.bin", root.0)),
+            test_tag_data(1, TagHash::NONE),
+        )?;
+        std::fs::write(dir.join("readme.txt"), "not a tag")?;
+
+        let mut source = DirectoryTagSource::new(&dir, Endian::Little)?;
+        source.insert_entry(root, TEST_ENTRY);
+        let result = source.read_tag_struct::<TestTag>(root);
+        std::fs::remove_dir_all(&dir)?;
+
+        assert_eq!(result?.value, 1);
+        assert_eq!(source.tags(), [root]);
+        assert_eq!(source.tags_by_reference(0x80801234), [root]);
+        assert!(source.read_tag_data(TagHash::new(1, 1)).is_err());
+
+        Ok(())
+    }
+}
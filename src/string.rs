@@ -1,30 +1,417 @@
-use crate::{error::Error, TigerReadable, TigerReader};
+use std::{collections::HashMap, io::SeekFrom, ops::Deref};
 
+use crate::{error::Error, Endian, FnvHash, Offset, TigerReadable, TigerReader};
+
+/// Default maximum length of null-terminated strings, in bytes for [`NullString`] and code units for [`WideString`]
+pub const DEFAULT_MAX_STRING_LENGTH: usize = 10240;
+
+/// Null-terminated string, decoded as UTF-8.
+///
+/// Strings that aren't valid UTF-8 are decoded as Latin-1 instead, so every byte is preserved.
+/// Strings longer than [`DEFAULT_MAX_STRING_LENGTH`] bytes return [`Error::StringTooLong`],
+/// use [`BoundedNullString`] for a different limit.
 #[derive(Debug, Clone)]
 pub struct NullString(pub String);
 
+impl NullString {
+    /// Reads a null-terminated string of at most `max_length` bytes, excluding the terminator
+    pub fn read_limited(reader: &mut dyn TigerReader, max_length: usize) -> crate::Result<Self> {
+        read_null_terminated(reader, max_length).map(|bytes| Self(decode_bytes(bytes)))
+    }
+}
+
 impl TigerReadable for NullString {
-    fn read_ds_endian(reader: &mut dyn TigerReader, _endian: crate::Endian) -> crate::Result<Self> {
-        let mut buf = String::new();
-
-        let mut b = [0u8; 1];
-        for _ in 0..10240 {
-            reader.read_exact(&mut b)?;
-            if b[0] == 0 {
-                return Ok(NullString(buf));
+    fn read_ds_endian(reader: &mut dyn TigerReader, _endian: Endian) -> crate::Result<Self> {
+        Self::read_limited(reader, DEFAULT_MAX_STRING_LENGTH)
+    }
+
+    const ID: Option<u32> = None;
+    const SIZE: usize = 0;
+}
+
+impl std::fmt::Display for NullString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// [`NullString`] with a maximum length of `MAX` bytes
+#[derive(Debug, Clone)]
+pub struct BoundedNullString<const MAX: usize>(pub String);
+
+impl<const MAX: usize> TigerReadable for BoundedNullString<MAX> {
+    fn read_ds_endian(reader: &mut dyn TigerReader, _endian: Endian) -> crate::Result<Self> {
+        NullString::read_limited(reader, MAX).map(|s| Self(s.0))
+    }
+
+    const ID: Option<u32> = None;
+    const SIZE: usize = 0;
+}
+
+impl<const MAX: usize> std::fmt::Display for BoundedNullString<MAX> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// String stored inline in a fixed-size `N` byte array, terminated by the first null byte or the end of the array.
+///
+/// Decoded the same way as [`NullString`].
+#[derive(Debug, Clone)]
+pub struct FixedString<const N: usize>(pub String);
+
+impl<const N: usize> TigerReadable for FixedString<N> {
+    fn read_ds_endian(reader: &mut dyn TigerReader, _endian: Endian) -> crate::Result<Self> {
+        let mut bytes = vec![0u8; N];
+        reader.read_exact(&mut bytes)?;
+        if let Some(end) = bytes.iter().position(|&b| b == 0) {
+            bytes.truncate(end);
+        }
+
+        Ok(Self(decode_bytes(bytes)))
+    }
+
+    const ID: Option<u32> = None;
+    const SIZE: usize = N;
+}
+
+impl<const N: usize> std::fmt::Display for FixedString<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Null-terminated UTF-16 string, in the endianness of the reader.
+///
+/// Unpaired surrogates are replaced with U+FFFD. Strings longer than [`DEFAULT_MAX_STRING_LENGTH`]
+/// code units return [`Error::StringTooLong`].
+#[derive(Debug, Clone)]
+pub struct WideString(pub String);
+
+impl WideString {
+    /// Reads a null-terminated UTF-16 string of at most `max_length` code units, excluding the terminator
+    pub fn read_limited(
+        reader: &mut dyn TigerReader,
+        endian: Endian,
+        max_length: usize,
+    ) -> crate::Result<Self> {
+        let start = reader.stream_position()?;
+        let mut units = vec![];
+        loop {
+            let unit = u16::read_ds_endian(reader, endian)?;
+            if unit == 0 {
+                return Ok(Self(String::from_utf16_lossy(&units)));
+            }
+
+            if units.len() == max_length {
+                return Err(Error::StringTooLong {
+                    max: max_length,
+                    offset: start,
+                });
             }
-            buf.push(b[0] as char);
+
+            units.push(unit);
         }
+    }
+}
 
-        Err(Error::StringTooLong)
+impl TigerReadable for WideString {
+    fn read_ds_endian(reader: &mut dyn TigerReader, endian: Endian) -> crate::Result<Self> {
+        Self::read_limited(reader, endian, DEFAULT_MAX_STRING_LENGTH)
     }
 
     const ID: Option<u32> = None;
     const SIZE: usize = 0;
 }
 
-impl std::fmt::Display for NullString {
+impl std::fmt::Display for WideString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// UTF-16 string stored inline in a fixed-size array of `N` code units, terminated by the first null unit or the end of the array
+#[derive(Debug, Clone)]
+pub struct FixedWideString<const N: usize>(pub String);
+
+impl<const N: usize> TigerReadable for FixedWideString<N> {
+    fn read_ds_endian(reader: &mut dyn TigerReader, endian: Endian) -> crate::Result<Self> {
+        let mut units: Vec<u16> = crate::read_inline_vec(reader, endian, N)?;
+        if let Some(end) = units.iter().position(|&u| u == 0) {
+            units.truncate(end);
+        }
+
+        Ok(Self(String::from_utf16_lossy(&units)))
+    }
+
+    const ID: Option<u32> = None;
+    const SIZE: usize = N * 2;
+}
+
+impl<const N: usize> std::fmt::Display for FixedWideString<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Relative pointer to a null-terminated string, such as a string in the string block of a tag.
+///
+/// Null pointers are read as an empty string without an offset.
+#[derive(Clone)]
+pub struct StringPointer(pub String, Option<u64>);
+
+impl StringPointer {
+    /// Absolute offset of the string, or `None` for a null pointer
+    pub fn offset(&self) -> Option<u64> {
+        self.1
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.1.is_none()
+    }
+}
+
+impl TigerReadable for StringPointer {
+    fn read_ds_endian(reader: &mut dyn TigerReader, endian: Endian) -> crate::Result<Self> {
+        let ptr_pos = reader.stream_position()?;
+        let offset = Offset::read_ds_endian(reader, endian)?;
+        if offset == 0 || offset == Offset::MAX {
+            return Ok(StringPointer(String::new(), None));
+        }
+
+        #[allow(clippy::unnecessary_cast)] // Offset is i32 with the 32bit feature
+        let ptr = ptr_pos.saturating_add_signed(offset as i64);
+        let save_pos = reader.stream_position()?;
+
+        reader.seek(SeekFrom::Start(ptr))?;
+        let string = NullString::read_ds_endian(reader, endian)?;
+        reader.seek(SeekFrom::Start(save_pos))?;
+
+        Ok(StringPointer(string.0, Some(ptr)))
+    }
+
+    const ID: Option<u32> = None;
+    const SIZE: usize = std::mem::size_of::<Offset>();
+}
+
+impl Deref for StringPointer {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for StringPointer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.1 {
+            Some(offset) => write!(f, "StringPointer({:?} @ 0x{offset:X})", self.0),
+            None => f.write_str("StringPointer(null)"),
+        }
+    }
+}
+
+impl std::fmt::Display for StringPointer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.0.fmt(f)
     }
 }
+
+/// Container of localized strings, keyed by string hash
+pub trait StringContainer {
+    fn get_string(&self, hash: FnvHash) -> Option<&str>;
+}
+
+impl StringContainer for HashMap<FnvHash, String> {
+    fn get_string(&self, hash: FnvHash) -> Option<&str> {
+        self.get(&hash).map(String::as_str)
+    }
+}
+
+/// Hash of a string in a localized string container.
+///
+/// Use [`Self::resolve`] with the string container of the tag (or a [`HashMap`] built from it) to get the string itself.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LocalizedString(pub FnvHash);
+
+impl LocalizedString {
+    pub const NONE: Self = Self(u32::MAX);
+
+    pub fn is_none(&self) -> bool {
+        self.0 == u32::MAX || self.0 == 0
+    }
+
+    pub fn resolve<'a>(&self, container: &'a (impl StringContainer + ?Sized)) -> Option<&'a str> {
+        if self.is_none() {
+            return None;
+        }
+
+        container.get_string(self.0)
+    }
+
+    /// Displays the resolved string, or the hash if it is not in `container`
+    pub fn display<'a>(
+        &self,
+        container: &'a (impl StringContainer + ?Sized),
+    ) -> impl std::fmt::Display + 'a {
+        let hash = *self;
+        let resolved = self.resolve(container);
+        DisplayFn(move |f: &mut std::fmt::Formatter<'_>| match resolved {
+            Some(string) => f.write_str(string),
+            None => std::fmt::Display::fmt(&hash, f),
+        })
+    }
+}
+
+impl TigerReadable for LocalizedString {
+    fn read_ds_endian(reader: &mut dyn TigerReader, endian: Endian) -> crate::Result<Self> {
+        Ok(Self(FnvHash::read_ds_endian(reader, endian)?))
+    }
+
+    const ID: Option<u32> = None;
+    const SIZE: usize = std::mem::size_of::<FnvHash>();
+}
+
+impl std::fmt::Debug for LocalizedString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LocalizedString({self})")
+    }
+}
+
+/// Displays the hash, as the string itself needs a container to be resolved
+impl std::fmt::Display for LocalizedString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_none() {
+            f.write_str("<none>")
+        } else {
+            write!(f, "str:{:08X}", self.0)
+        }
+    }
+}
+
+pub(crate) struct DisplayFn<F>(pub(crate) F);
+
+impl<F: Fn(&mut std::fmt::Formatter<'_>) -> std::fmt::Result> std::fmt::Display for DisplayFn<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self.0)(f)
+    }
+}
+
+/// Size of the chunks null-terminated strings are read in
+const READ_CHUNK_SIZE: usize = 64;
+
+/// Reads bytes up to a null terminator, leaving the reader right after it
+fn read_null_terminated(reader: &mut dyn TigerReader, max_length: usize) -> crate::Result<Vec<u8>> {
+    let start = reader.stream_position()?;
+    let mut bytes = vec![];
+    let mut chunk = [0u8; READ_CHUNK_SIZE];
+    loop {
+        let read = reader.read(&mut chunk)?;
+        if read == 0 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+
+        let chunk = &chunk[..read];
+        if let Some(end) = chunk.iter().position(|&b| b == 0) {
+            bytes.extend_from_slice(&chunk[..end]);
+            if bytes.len() <= max_length {
+                reader.seek(SeekFrom::Start(start + bytes.len() as u64 + 1))?;
+                return Ok(bytes);
+            }
+        } else {
+            bytes.extend_from_slice(chunk);
+        }
+
+        if bytes.len() > max_length {
+            return Err(Error::StringTooLong {
+                max: max_length,
+                offset: start,
+            });
+        }
+    }
+}
+
+/// Decodes bytes as UTF-8, falling back to Latin-1 for invalid UTF-8
+fn decode_bytes(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes)
+        .unwrap_or_else(|e| e.into_bytes().into_iter().map(char::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        io::{Cursor, Seek},
+    };
+
+    use super::{
+        BoundedNullString, FixedString, FixedWideString, LocalizedString, NullString,
+        StringPointer, WideString,
+    };
+    use crate::{error::Error, Endian, TigerReadable};
+
+    #[test]
+    fn test_strings() -> crate::Result<()> {
+        let data = b"\xC3\xA9t\xE9\0next";
+        let mut cursor = Cursor::new(&data[..]);
+        // Invalid UTF-8, decoded as Latin-1
+        assert_eq!(NullString::read_ds(&mut cursor)?.0, "Ã©té");
+        assert_eq!(cursor.stream_position()?, 5);
+
+        let mut cursor = Cursor::new(&data[..2]);
+        assert_eq!(FixedString::<2>::read_ds(&mut cursor)?.0, "é");
+
+        let mut cursor = Cursor::new(&data[..]);
+        assert!(matches!(
+            BoundedNullString::<3>::read_ds(&mut cursor),
+            Err(Error::StringTooLong { max: 3, offset: 0 })
+        ));
+
+        let mut cursor = Cursor::new(&data[5..]);
+        assert!(matches!(
+            NullString::read_ds(&mut cursor),
+            Err(Error::Io(_))
+        ));
+
+        let mut cursor = Cursor::new(&[0, b'h', 0, b'i', 0, 0, 0, b'!'][..]);
+        assert_eq!(
+            WideString::read_ds_endian(&mut cursor, Endian::Big)?.0,
+            "hi"
+        );
+        assert_eq!(cursor.stream_position()?, 6);
+
+        let mut cursor = Cursor::new(&[b'h', 0, b'i', 0, 0, 0, b'!', 0][..]);
+        assert_eq!(FixedWideString::<4>::read_ds(&mut cursor)?.0, "hi");
+        assert_eq!(cursor.stream_position()?, 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_references() -> crate::Result<()> {
+        let pointer_size = StringPointer::SIZE;
+        let mut data = vec![0u8; pointer_size * 2];
+        data[..pointer_size]
+            .copy_from_slice(&(pointer_size as u64 * 2).to_le_bytes()[..pointer_size]);
+        data.extend_from_slice(b"name\0");
+
+        let mut cursor = Cursor::new(&data);
+        let [name, null]: [StringPointer; 2] = TigerReadable::read_ds(&mut cursor)?;
+        assert_eq!(&*name, "name");
+        assert_eq!(name.offset(), Some(pointer_size as u64 * 2));
+        assert!(null.is_null());
+        assert_eq!(cursor.stream_position()?, pointer_size as u64 * 2);
+
+        let container = HashMap::from([(0x1234, "Hello".to_string())]);
+        let hello = LocalizedString(0x1234);
+        assert_eq!(hello.resolve(&container), Some("Hello"));
+        assert_eq!(hello.display(&container).to_string(), "Hello");
+        assert_eq!(
+            LocalizedString(0x5678).display(&container).to_string(),
+            "str:00005678"
+        );
+        assert_eq!(LocalizedString::NONE.resolve(&container), None);
+
+        Ok(())
+    }
+}
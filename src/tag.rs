@@ -0,0 +1,180 @@
+use std::{
+    any::Any,
+    fmt::{Debug, Display, Formatter},
+    hash::Hash,
+    marker::PhantomData,
+    sync::Arc,
+};
+
+use tiger_pkg::{TagHash, TagHash64};
+
+use crate::{cache::TagCache, dpkg::PackageManagerExt, error::Error, TigerReadable, TigerReader};
+
+macro_rules! typed_tag {
+    (
+        $(#[$attr:meta])*
+        $name:ident($hash:ident), $read:ident, $read_cached:ident, $is_some:expr
+    ) => {
+        $(#[$attr])*
+        pub struct $name<T: TigerReadable> {
+            hash: $hash,
+            _marker: PhantomData<fn() -> T>,
+        }
+
+        impl<T: TigerReadable> $name<T> {
+            pub const NONE: Self = Self::new($hash::NONE);
+
+            pub const fn new(hash: $hash) -> Self {
+                Self {
+                    hash,
+                    _marker: PhantomData,
+                }
+            }
+
+            pub fn hash(&self) -> $hash {
+                self.hash
+            }
+
+            pub fn is_some(&self) -> bool {
+                let is_some: fn(&$hash) -> bool = $is_some;
+                is_some(&self.hash)
+            }
+
+            pub fn is_none(&self) -> bool {
+                !self.is_some()
+            }
+
+            /// Reads the referenced tag, checking its type against `T`.
+            ///
+            /// Returns [`Error::PointerNull`] if the reference is empty.
+            pub fn load(&self, package_manager: &(impl PackageManagerExt + ?Sized)) -> crate::Result<T> {
+                if self.is_none() {
+                    return Err(Error::PointerNull);
+                }
+
+                package_manager.$read(self.hash)
+            }
+
+            /// Reads the referenced tag, or returns `None` if the reference is empty
+            pub fn load_optional(
+                &self,
+                package_manager: &(impl PackageManagerExt + ?Sized),
+            ) -> crate::Result<Option<T>> {
+                if self.is_none() {
+                    return Ok(None);
+                }
+
+                package_manager.$read(self.hash).map(Some)
+            }
+
+            /// Reads the referenced tag through `cache`, see [`Self::load`]
+            pub fn load_cached(
+                &self,
+                package_manager: &(impl PackageManagerExt + ?Sized),
+                cache: &TagCache,
+            ) -> crate::Result<Arc<T>>
+            where
+                T: Any + Send + Sync,
+            {
+                if self.is_none() {
+                    return Err(Error::PointerNull);
+                }
+
+                package_manager.$read_cached(cache, self.hash)
+            }
+        }
+
+        impl<T: TigerReadable> TigerReadable for $name<T> {
+            fn read_ds_endian(
+                reader: &mut dyn TigerReader,
+                endian: crate::Endian,
+            ) -> crate::Result<Self> {
+                Ok(Self::new($hash::read_ds_endian(reader, endian)?))
+            }
+
+            const SIZE: usize = $hash::SIZE;
+        }
+
+        impl<T: TigerReadable> From<$hash> for $name<T> {
+            fn from(hash: $hash) -> Self {
+                Self::new(hash)
+            }
+        }
+
+        impl<T: TigerReadable> From<$name<T>> for $hash {
+            fn from(tag: $name<T>) -> Self {
+                tag.hash
+            }
+        }
+
+        impl<T: TigerReadable> Clone for $name<T> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<T: TigerReadable> Copy for $name<T> {}
+
+        impl<T: TigerReadable> PartialEq for $name<T> {
+            fn eq(&self, other: &Self) -> bool {
+                self.hash == other.hash
+            }
+        }
+
+        impl<T: TigerReadable> Eq for $name<T> {}
+
+        impl<T: TigerReadable> Hash for $name<T> {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.hash.hash(state)
+            }
+        }
+
+        impl<T: TigerReadable> Debug for $name<T> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                f.write_fmt(format_args!(
+                    concat!(stringify!($name), "<{}>({})"),
+                    crate::ShortName::of::<T>(),
+                    self.hash
+                ))
+            }
+        }
+
+        impl<T: TigerReadable> Display for $name<T> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                Display::fmt(&self.hash, f)
+            }
+        }
+    };
+}
+
+typed_tag! {
+    /// Reference to a tag of type `T`, loaded on demand through [`PackageManagerExt`]
+    Tag(TagHash), read_tag_struct, read_tag_struct_cached, TagHash::is_some
+}
+
+typed_tag! {
+    /// 64-bit reference to a tag of type `T`, loaded on demand through [`PackageManagerExt`]
+    Tag64(TagHash64), read_tag64_struct, read_tag64_struct_cached, |hash| hash.0 != 0 && hash.0 != u64::MAX
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use tiger_pkg::TagHash;
+
+    use super::Tag;
+    use crate::TigerReadable;
+
+    #[test]
+    fn test_tag() -> crate::Result<()> {
+        let data = 0x80801234u32.to_le_bytes();
+        let tag: Tag<u32> =
+            TigerReadable::read_ds_endian(&mut Cursor::new(&data), crate::Endian::Little)?;
+        assert_eq!(tag.hash(), TagHash(0x80801234));
+        assert!(tag.is_some());
+        assert!(Tag::<u32>::NONE.is_none());
+
+        Ok(())
+    }
+}
@@ -11,6 +11,7 @@ macro_rules! tuple_impls {
 
 
             const SIZE: usize = 0 $(+ $name::SIZE)+;
+            const VARIABLE_SIZE: bool = false $(|| $name::VARIABLE_SIZE)+;
         }
     };
 }
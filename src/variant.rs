@@ -1,23 +1,114 @@
-use std::io::SeekFrom;
+use std::io::{Read, SeekFrom};
 
-use crate::{error::Error, Offset, TigerReadable, TigerReader};
+use crate::{error::Error, Endian, Offset, TigerReadable, TigerReader};
+
+/// Which part of the resource header a variant enum dispatches on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariantDispatch {
+    /// The `resource_type` preceding the pointer target
+    ResourceType,
+    /// The `class_type` of the class header following the resource type (see `ResourcePointerWithClass`)
+    ClassType,
+    /// Both the `resource_type` and the `class_type`
+    Combined,
+}
+
+impl VariantDispatch {
+    /// Whether the class header (`parent_tag` and `class_type`) has to be read
+    pub const fn reads_class_header(&self) -> bool {
+        !matches!(self, Self::ResourceType)
+    }
+}
+
+/// Header preceding a resource, read by variant pointers before dispatching
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VariantHeader {
+    pub resource_type: u32,
+    /// Only read for dispatch modes that use the class header
+    pub parent_tag: Option<u32>,
+    /// Only read for dispatch modes that use the class header
+    pub class_type: Option<u32>,
+}
+
+impl VariantHeader {
+    /// Reads the header for the given dispatch mode, starting at the resource type
+    pub fn read(
+        reader: &mut dyn TigerReader,
+        endian: Endian,
+        dispatch: VariantDispatch,
+    ) -> crate::Result<Self> {
+        let resource_type = TigerReadable::read_ds_endian(reader, endian)?;
+        if !dispatch.reads_class_header() {
+            return Ok(Self {
+                resource_type,
+                parent_tag: None,
+                class_type: None,
+            });
+        }
+
+        Ok(Self {
+            resource_type,
+            parent_tag: Some(TigerReadable::read_ds_endian(reader, endian)?),
+            class_type: Some(TigerReadable::read_ds_endian(reader, endian)?),
+        })
+    }
+}
 
 pub trait VariantEnum: Sized {
     const EXTRA_OFFSET: i64 = 0;
 
+    /// Which part of the resource header selects the variant
+    const DISPATCH: VariantDispatch = VariantDispatch::ResourceType;
+
+    /// Reads the variant selected by `class`, which is the resource type or class type depending on [`Self::DISPATCH`]
     fn read_variant_endian(
         reader: &mut dyn TigerReader,
         endian: crate::Endian,
         class: u32,
     ) -> crate::Result<Self>;
+
+    /// Reads the variant selected by the resource header.
+    ///
+    /// Enums using [`VariantDispatch::Combined`] must override this method.
+    fn read_variant_header(
+        reader: &mut dyn TigerReader,
+        endian: crate::Endian,
+        header: &VariantHeader,
+    ) -> crate::Result<Self> {
+        match Self::DISPATCH {
+            VariantDispatch::ResourceType => {
+                Self::read_variant_endian(reader, endian, header.resource_type)
+            }
+            VariantDispatch::ClassType => {
+                let class = header.class_type.ok_or_else(|| {
+                    Error::InvalidStructure(format!(
+                        "Missing class header for variant enum {}",
+                        crate::ShortName::of::<Self>()
+                    ))
+                })?;
+                Self::read_variant_endian(reader, endian, class)
+            }
+            VariantDispatch::Combined => Err(Error::InvalidStructure(format!(
+                "Variant enum {} does not implement combined dispatch",
+                crate::ShortName::of::<Self>()
+            ))),
+        }
+    }
 }
 
+/// Declares a variant enum whose variants are named after their structs.
+///
+/// Prefer the [`tiger_variant`](crate::tiger_variant) attribute for new code, which supports
+/// arbitrary attributes, renamed variants and explicit class IDs.
+///
+/// `[impl TigerVisit, TigerDiff]` implements the listed traits when their features are enabled.
 #[macro_export]
 macro_rules! tiger_variant_enum {
     (
         $(#[derive($($derive:ident),+)])?
         $([offset = $offset:expr])?
         $([Unknown($enable_unknown:expr)])?
+        $([impl $($impls:ident),+])?
         enum $enum_name:ident {
             $($variant:ident),*
         }
@@ -101,11 +192,73 @@ macro_rules! tiger_variant_enum {
                 }
             }
         }
+
+        $crate::__tiger_variant_enum_impls!([$($($impls),+)?] $enum_name { $($variant),* });
     };
 }
 
+/// Implements the traits listed in `[impl TigerVisit, TigerDiff]` for enums declared with `tiger_variant_enum!`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tiger_variant_enum_impls {
+    ([] $($enum:tt)*) => {};
+    ([TigerVisit $(, $impls:ident)*] $($enum:tt)*) => {
+        $crate::__tiger_variant_enum_visit!($($enum)*);
+        $crate::__tiger_variant_enum_impls!([$($impls),*] $($enum)*);
+    };
+    ([TigerDiff $(, $impls:ident)*] $($enum:tt)*) => {
+        $crate::__tiger_variant_enum_diff!($($enum)*);
+        $crate::__tiger_variant_enum_impls!([$($impls),*] $($enum)*);
+    };
+}
+
+/// Implements `TigerVisit` for enums declared with `tiger_variant_enum!`, no-op without the `visit` feature
+#[cfg(not(feature = "visit"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tiger_variant_enum_visit {
+    ($($tt:tt)*) => {};
+}
+
+/// Implements `TigerDiff` for enums declared with `tiger_variant_enum!`, no-op without the `diff` feature
+#[cfg(not(feature = "diff"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tiger_variant_enum_diff {
+    ($($tt:tt)*) => {};
+}
+
+/// Reads the raw bytes of an unknown variant, used by `#[tiger_variant]` enums with a `data` field in their unknown variant.
+///
+/// Reads up to `max_size` bytes, stopping early at the end of the stream or at the class header of the next known object.
+pub fn read_unknown_variant_data(
+    reader: &mut dyn TigerReader,
+    endian: Endian,
+    max_size: usize,
+    known_classes: &[u32],
+) -> crate::Result<Vec<u8>> {
+    let mut data = Vec::with_capacity(max_size);
+    reader.take(max_size as u64).read_to_end(&mut data)?;
+
+    let next_object = data.chunks_exact(4).position(|chunk| {
+        let chunk = chunk.try_into().unwrap();
+        let class = match endian {
+            Endian::Little => u32::from_le_bytes(chunk),
+            Endian::Big => u32::from_be_bytes(chunk),
+        };
+
+        known_classes.contains(&class)
+    });
+
+    if let Some(next_object) = next_object {
+        data.truncate(next_object * 4);
+    }
+
+    Ok(data)
+}
+
 #[derive(Debug)]
-pub struct OptionalVariantPointer<T: VariantEnum + Sized>(Option<T>);
+pub struct OptionalVariantPointer<T: VariantEnum + Sized>(pub(crate) Option<T>);
 
 impl<T: VariantEnum + Sized> TigerReadable for OptionalVariantPointer<T> {
     fn read_ds_endian(reader: &mut dyn TigerReader, endian: crate::Endian) -> crate::Result<Self> {
@@ -119,10 +272,10 @@ impl<T: VariantEnum + Sized> TigerReadable for OptionalVariantPointer<T> {
 
         reader.seek(SeekFrom::Start(offset_base))?;
         reader.seek(SeekFrom::Current(offset as i64 - 4))?;
-        let resource_type: u32 = TigerReadable::read_ds_endian(reader, endian)?;
+        let header = VariantHeader::read(reader, endian, T::DISPATCH)?;
         reader.seek(SeekFrom::Start(offset_base))?;
         reader.seek(SeekFrom::Current(offset as i64 + T::EXTRA_OFFSET))?;
-        let data = T::read_variant_endian(reader, endian, resource_type)?;
+        let data = T::read_variant_header(reader, endian, &header)?;
 
         reader.seek(SeekFrom::Start(offset_save))?;
 
@@ -149,7 +302,7 @@ impl<T: VariantEnum + Sized + Clone> Clone for OptionalVariantPointer<T> {
 }
 
 #[derive(Debug)]
-pub struct VariantPointer<T: VariantEnum + Sized>(T);
+pub struct VariantPointer<T: VariantEnum + Sized>(pub(crate) T);
 
 impl<T: VariantEnum + Sized> TigerReadable for VariantPointer<T> {
     fn read_ds_endian(reader: &mut dyn TigerReader, endian: crate::Endian) -> crate::Result<Self> {
@@ -175,3 +328,35 @@ impl<T: VariantEnum + Sized + Clone> Clone for VariantPointer<T> {
         Self(self.0.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::read_unknown_variant_data;
+    use crate::Endian;
+
+    #[test]
+    fn test_unknown_variant_data() -> crate::Result<()> {
+        const DATA: &[u8] = &[
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x44, 0x33, 0x22, 0x11, 0x09, 0x0A,
+        ];
+
+        let data = read_unknown_variant_data(&mut Cursor::new(DATA), Endian::Little, 6, &[])?;
+        assert_eq!(data, &DATA[..6]);
+
+        let data = read_unknown_variant_data(
+            &mut Cursor::new(DATA),
+            Endian::Little,
+            0x100,
+            &[0x11223344],
+        )?;
+        assert_eq!(data, &DATA[..8]);
+
+        let data =
+            read_unknown_variant_data(&mut Cursor::new(DATA), Endian::Big, 0x100, &[0x11223344])?;
+        assert_eq!(data, DATA);
+
+        Ok(())
+    }
+}
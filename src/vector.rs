@@ -1,7 +1,16 @@
-use crate::{error::Error, Offset, ResultExt, Size, TigerReadable, TigerReader};
+use std::{
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    array::VARIABLE_SIZE_MESSAGE, error::Error, Offset, ResultExt, Size, TigerReadable, TigerReader,
+};
 
 impl<T: TigerReadable> TigerReadable for Vec<T> {
     fn read_ds_endian(reader: &mut dyn TigerReader, endian: crate::Endian) -> crate::Result<Self> {
+        const { assert!(!T::VARIABLE_SIZE, "{}", VARIABLE_SIZE_MESSAGE) };
         let size = Size::read_ds_endian(reader, endian)? as usize;
         let ptr = reader.stream_position()? + Offset::read_ds_endian(reader, endian)? as u64;
         let save_pos = reader.stream_position()?;
@@ -57,3 +66,99 @@ impl<T: TigerReadable> TigerReadable for Vec<T> {
     const ID: Option<u32> = None;
     const SIZE: usize = std::mem::size_of::<(Size, Offset)>();
 }
+
+/// Reads `count` elements stored inline at the current position of the reader
+pub fn read_inline_vec<T: TigerReadable>(
+    reader: &mut dyn TigerReader,
+    endian: crate::Endian,
+    count: usize,
+) -> crate::Result<Vec<T>> {
+    let mut data = Vec::with_capacity(count);
+    for i in 0..count {
+        data.push(T::read_ds_endian(reader, endian).with_array_element(i)?);
+    }
+
+    Ok(data)
+}
+
+/// Inline array prefixed by its element count, eg. a `u32` count followed by that many elements.
+///
+/// The elements are read in place rather than through a relative offset like [`Vec<T>`].
+/// `SIZE` only covers the count, so structs containing an `InlineVec` are variable-size and can't declare an explicit size.
+pub struct InlineVec<C, T>(pub Vec<T>, PhantomData<C>);
+
+impl<C, T> TigerReadable for InlineVec<C, T>
+where
+    C: TigerReadable + TryInto<usize>,
+    T: TigerReadable,
+{
+    fn read_ds_endian(reader: &mut dyn TigerReader, endian: crate::Endian) -> crate::Result<Self> {
+        let count_pos = reader.stream_position()?;
+        let count: usize = C::read_ds_endian(reader, endian)?.try_into().map_err(|_| {
+            Error::InvalidStructure(format!(
+                "Inline array count at 0x{count_pos:X} does not fit in a usize ({typename})",
+                typename = std::any::type_name::<Self>(),
+            ))
+        })?;
+
+        Ok(InlineVec(
+            read_inline_vec(reader, endian, count)?,
+            PhantomData,
+        ))
+    }
+
+    const ID: Option<u32> = None;
+    const SIZE: usize = C::SIZE;
+    const VARIABLE_SIZE: bool = true;
+}
+
+impl<C, T> From<Vec<T>> for InlineVec<C, T> {
+    fn from(data: Vec<T>) -> Self {
+        InlineVec(data, PhantomData)
+    }
+}
+
+impl<C, T> Deref for InlineVec<C, T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<C, T> DerefMut for InlineVec<C, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<C, T: Debug> Debug for InlineVec<C, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("InlineVec").field(&self.0).finish()
+    }
+}
+
+impl<C, T: Clone> Clone for InlineVec<C, T> {
+    fn clone(&self) -> Self {
+        InlineVec(self.0.clone(), PhantomData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{InlineVec, TigerReadable};
+
+    #[test]
+    fn test_inline_vec() -> crate::Result<()> {
+        const DATA: &[u8] = &[3, 0, 0, 0, 1, 0, 2, 0, 3, 0, 0xFF];
+        let mut cursor = Cursor::new(&DATA);
+
+        let v = <InlineVec<u32, u16>>::read_ds(&mut cursor)?;
+        assert_eq!(*v, [1, 2, 3]);
+        assert_eq!(cursor.position(), 10);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,798 @@
+//! Generic traversal of parsed values.
+//!
+//! `tiger_type` structs declared with `#[tiger_type(visit)]` implement [`TigerVisit`] when the `visit` feature is enabled,
+//! as do `#[tiger_variant(visit)]` enums. Hand-written `TigerReadable` types used as fields need an implementation as well,
+//! an empty `impl TigerVisit for MyType {}` treats the type as a leaf. Fields can be left out with `#[tiger(skip_visit)]`,
+//! fields read with `read_with` or `map` are always left out.
+
+use std::{
+    any::Any,
+    fmt::{Display, Formatter},
+};
+
+#[cfg(feature = "tiger_pkg")]
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[cfg(feature = "tiger_pkg")]
+use tiger_pkg::{TagHash, TagHash64};
+
+#[cfg(feature = "tiger_pkg")]
+use crate::source::TagSource;
+use crate::{
+    pointer::{
+        LazyResourcePointer, Pointer, PointerOptional, ResourcePointer, TypedResourcePointer,
+        TypedResourcePointerOptional,
+    },
+    variant::{OptionalVariantPointer, VariantEnum, VariantPointer},
+    BoundedNullString, FixedString, FixedWideString, HashedName, InlineVec, LocalizedString,
+    NullString, Padding, StringPointer, TigerReadable, WideString,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// Struct field, or the index of a tuple struct field
+    Field(&'static str),
+    /// Element of a `Vec`, array or tuple
+    Index(usize),
+    /// Variant of a variant enum
+    Variant(&'static str),
+}
+
+/// Path from the visited root to a value, eg. `meshes[2].parts[0].material`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FieldPath(Vec<PathSegment>);
+
+impl FieldPath {
+    pub fn push(&mut self, segment: PathSegment) {
+        self.0.push(segment);
+    }
+
+    pub fn pop(&mut self) -> Option<PathSegment> {
+        self.0.pop()
+    }
+
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns this path with `other` appended to it
+    pub fn join(&self, other: &FieldPath) -> FieldPath {
+        FieldPath(self.0.iter().chain(other.0.iter()).copied().collect())
+    }
+}
+
+impl Display for FieldPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Field(name) => {
+                    if i > 0 {
+                        f.write_str(".")?;
+                    }
+                    f.write_str(name)?;
+                }
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+                PathSegment::Variant(name) => write!(f, "({name})")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Receives every value visited by [`TigerVisit::visit`]
+pub trait Visitor {
+    /// Called for every value, before its fields are visited
+    fn visit(&mut self, path: &FieldPath, value: &dyn Any) {
+        let _ = (path, value);
+    }
+
+    /// Called for every tag reference, after [`Self::visit`] is called for the referencing value
+    #[cfg(feature = "tiger_pkg")]
+    fn visit_tag(&mut self, path: &FieldPath, reference: &TagReference) {
+        let _ = (path, reference);
+    }
+}
+
+impl<F: FnMut(&FieldPath, &dyn Any)> Visitor for F {
+    fn visit(&mut self, path: &FieldPath, value: &dyn Any) {
+        self(path, value)
+    }
+}
+
+/// Receives every value visited by [`TigerVisit::visit_mut`]
+pub trait VisitorMut {
+    /// Called for every value, before its fields are visited
+    fn visit_mut(&mut self, path: &FieldPath, value: &mut dyn Any);
+}
+
+impl<F: FnMut(&FieldPath, &mut dyn Any)> VisitorMut for F {
+    fn visit_mut(&mut self, path: &FieldPath, value: &mut dyn Any) {
+        self(path, value)
+    }
+}
+
+pub trait TigerVisit: Any {
+    /// Visits this value and all of its fields
+    fn visit(&self, path: &mut FieldPath, visitor: &mut dyn Visitor)
+    where
+        Self: Sized,
+    {
+        visitor.visit(path, self);
+        self.visit_fields(path, visitor);
+    }
+
+    /// Visits the fields of this value, without visiting the value itself
+    fn visit_fields(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        let _ = (path, visitor);
+    }
+
+    /// Mutably visits this value and all of its fields.
+    ///
+    /// Fields are visited after the visitor returns, so replacing a value also changes which fields are visited.
+    fn visit_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut)
+    where
+        Self: Sized,
+    {
+        visitor.visit_mut(path, self);
+        self.visit_fields_mut(path, visitor);
+    }
+
+    /// Mutably visits the fields of this value, without visiting the value itself
+    fn visit_fields_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut) {
+        let _ = (path, visitor);
+    }
+}
+
+/// Visits `value` and all of its fields, starting from an empty path
+pub fn walk<T: TigerVisit>(value: &T, visitor: &mut dyn Visitor) {
+    value.visit(&mut FieldPath::default(), visitor);
+}
+
+/// Mutably visits `value` and all of its fields, starting from an empty path
+pub fn walk_mut<T: TigerVisit>(value: &mut T, visitor: &mut dyn VisitorMut) {
+    value.visit_mut(&mut FieldPath::default(), visitor);
+}
+
+/// Returns a copy of every value of type `V` in `value`, along with its path
+pub fn collect<V: Any + Clone, T: TigerVisit>(value: &T) -> Vec<(FieldPath, V)> {
+    let mut values = vec![];
+    for_each(value, |path, v: &V| values.push((path.clone(), v.clone())));
+    values
+}
+
+/// Calls `f` for every value of type `V` in `value`
+pub fn for_each<V: Any, T: TigerVisit>(value: &T, mut f: impl FnMut(&FieldPath, &V)) {
+    walk(value, &mut |path: &FieldPath, v: &dyn Any| {
+        if let Some(v) = v.downcast_ref() {
+            f(path, v);
+        }
+    });
+}
+
+/// Calls `f` for every value of type `V` in `value`, allowing it to be modified
+pub fn for_each_mut<V: Any, T: TigerVisit>(value: &mut T, mut f: impl FnMut(&FieldPath, &mut V)) {
+    walk_mut(value, &mut |path: &FieldPath, v: &mut dyn Any| {
+        if let Some(v) = v.downcast_mut() {
+            f(path, v);
+        }
+    });
+}
+
+/// Visits `value` under `segment`, used by generated implementations
+#[doc(hidden)]
+pub fn visit_child<T: TigerVisit>(
+    value: &T,
+    segment: PathSegment,
+    path: &mut FieldPath,
+    visitor: &mut dyn Visitor,
+) {
+    path.push(segment);
+    value.visit(path, visitor);
+    path.pop();
+}
+
+/// Mutably visits `value` under `segment`, used by generated implementations
+#[doc(hidden)]
+pub fn visit_child_mut<T: TigerVisit>(
+    value: &mut T,
+    segment: PathSegment,
+    path: &mut FieldPath,
+    visitor: &mut dyn VisitorMut,
+) {
+    path.push(segment);
+    value.visit_mut(path, visitor);
+    path.pop();
+}
+
+macro_rules! impl_visit_leaf {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl TigerVisit for $ty {}
+        )+
+    };
+}
+
+impl_visit_leaf! {
+    (), bool, u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64,
+    glam::Vec2, glam::Vec3, glam::Vec4, glam::IVec2, glam::IVec3, glam::IVec4, glam::Quat, glam::Mat4,
+    NullString, WideString, StringPointer, LocalizedString, HashedName, ResourcePointer,
+}
+
+#[cfg(feature = "tiger_pkg")]
+impl_visit_leaf!(crate::ResourcePointerWithClass);
+
+impl<const N: usize> TigerVisit for Padding<N> {}
+impl<const MAX: usize> TigerVisit for BoundedNullString<MAX> {}
+impl<const N: usize> TigerVisit for FixedString<N> {}
+impl<const N: usize> TigerVisit for FixedWideString<N> {}
+
+impl<T: TigerVisit> TigerVisit for Vec<T> {
+    fn visit_fields(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        for (i, value) in self.iter().enumerate() {
+            visit_child(value, PathSegment::Index(i), path, visitor);
+        }
+    }
+
+    fn visit_fields_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut) {
+        for (i, value) in self.iter_mut().enumerate() {
+            visit_child_mut(value, PathSegment::Index(i), path, visitor);
+        }
+    }
+}
+
+impl<T: TigerVisit, const N: usize> TigerVisit for [T; N] {
+    fn visit_fields(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        for (i, value) in self.iter().enumerate() {
+            visit_child(value, PathSegment::Index(i), path, visitor);
+        }
+    }
+
+    fn visit_fields_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut) {
+        for (i, value) in self.iter_mut().enumerate() {
+            visit_child_mut(value, PathSegment::Index(i), path, visitor);
+        }
+    }
+}
+
+impl<C: 'static, T: TigerVisit> TigerVisit for InlineVec<C, T> {
+    fn visit_fields(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        self.0.visit_fields(path, visitor);
+    }
+
+    fn visit_fields_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut) {
+        self.0.visit_fields_mut(path, visitor);
+    }
+}
+
+macro_rules! tuple_visit_impls {
+    ( $( $name:ident $index:tt )+ ) => {
+        impl<$($name: TigerVisit),+> TigerVisit for ($($name,)+) {
+            fn visit_fields(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+                $(
+                    visit_child(&self.$index, PathSegment::Index($index), path, visitor);
+                )+
+            }
+
+            fn visit_fields_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut) {
+                $(
+                    visit_child_mut(&mut self.$index, PathSegment::Index($index), path, visitor);
+                )+
+            }
+        }
+    };
+}
+
+tuple_visit_impls! { A 0 }
+tuple_visit_impls! { A 0 B 1 }
+tuple_visit_impls! { A 0 B 1 C 2 }
+tuple_visit_impls! { A 0 B 1 C 2 D 3 }
+tuple_visit_impls! { A 0 B 1 C 2 D 3 E 4 }
+tuple_visit_impls! { A 0 B 1 C 2 D 3 E 4 F 5 }
+tuple_visit_impls! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 }
+tuple_visit_impls! { A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 }
+
+// Wrappers are transparent, their contents are visited under the same path
+
+impl<T: TigerVisit> TigerVisit for Option<T> {
+    fn visit(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        if let Some(value) = self {
+            value.visit(path, visitor);
+        }
+    }
+
+    fn visit_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut) {
+        if let Some(value) = self {
+            value.visit_mut(path, visitor);
+        }
+    }
+}
+
+impl<T: TigerVisit> TigerVisit for Box<T> {
+    fn visit(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        (**self).visit(path, visitor);
+    }
+
+    fn visit_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut) {
+        (**self).visit_mut(path, visitor);
+    }
+}
+
+impl<T: TigerReadable + TigerVisit> TigerVisit for Pointer<T> {
+    fn visit(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        self.0.visit(path, visitor);
+    }
+
+    fn visit_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut) {
+        self.0.visit_mut(path, visitor);
+    }
+}
+
+impl<T: TigerReadable + TigerVisit> TigerVisit for PointerOptional<T> {
+    fn visit(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        self.0.visit(path, visitor);
+    }
+
+    fn visit_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut) {
+        self.0.visit_mut(path, visitor);
+    }
+}
+
+impl<T: TigerReadable + TigerVisit> TigerVisit for TypedResourcePointer<T> {
+    fn visit(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        self.0.visit(path, visitor);
+    }
+
+    fn visit_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut) {
+        self.0.visit_mut(path, visitor);
+    }
+}
+
+impl<T: TigerReadable + TigerVisit> TigerVisit for TypedResourcePointerOptional<T> {
+    fn visit(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        self.0.visit(path, visitor);
+    }
+
+    fn visit_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut) {
+        self.0.visit_mut(path, visitor);
+    }
+}
+
+impl<T: TigerReadable + 'static> TigerVisit for LazyResourcePointer<T> {}
+
+impl<T: VariantEnum + TigerVisit> TigerVisit for OptionalVariantPointer<T> {
+    fn visit(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        self.0.visit(path, visitor);
+    }
+
+    fn visit_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut) {
+        self.0.visit_mut(path, visitor);
+    }
+}
+
+impl<T: VariantEnum + TigerVisit> TigerVisit for VariantPointer<T> {
+    fn visit(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        self.0.visit(path, visitor);
+    }
+
+    fn visit_mut(&mut self, path: &mut FieldPath, visitor: &mut dyn VisitorMut) {
+        self.0.visit_mut(path, visitor);
+    }
+}
+
+/// Implements `TigerVisit` for enums declared with `tiger_variant_enum!`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tiger_variant_enum_visit {
+    ($enum_name:ident { $($variant:ident),* }) => {
+        impl $crate::visit::TigerVisit for $enum_name {
+            fn visit_fields(
+                &self,
+                path: &mut $crate::visit::FieldPath,
+                visitor: &mut dyn $crate::visit::Visitor,
+            ) {
+                #[allow(unreachable_patterns)]
+                match self {
+                    $(
+                        Self::$variant(v) => $crate::visit::visit_child(
+                            v,
+                            $crate::visit::PathSegment::Variant(stringify!($variant)),
+                            path,
+                            visitor,
+                        ),
+                    )*
+                    _ => {}
+                }
+            }
+
+            fn visit_fields_mut(
+                &mut self,
+                path: &mut $crate::visit::FieldPath,
+                visitor: &mut dyn $crate::visit::VisitorMut,
+            ) {
+                #[allow(unreachable_patterns)]
+                match self {
+                    $(
+                        Self::$variant(v) => $crate::visit::visit_child_mut(
+                            v,
+                            $crate::visit::PathSegment::Variant(stringify!($variant)),
+                            path,
+                            visitor,
+                        ),
+                    )*
+                    _ => {}
+                }
+            }
+        }
+    };
+}
+
+/// Hash of a referenced tag
+#[cfg(feature = "tiger_pkg")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TagReferenceHash {
+    Tag(TagHash),
+    Tag64(TagHash64),
+}
+
+#[cfg(feature = "tiger_pkg")]
+impl Display for TagReferenceHash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagReferenceHash::Tag(tag) => tag.fmt(f),
+            TagReferenceHash::Tag64(hash) => hash.fmt(f),
+        }
+    }
+}
+
+/// Reads a tag as a known type and collects the tags it references
+#[cfg(feature = "tiger_pkg")]
+pub type FollowTagFn = fn(&dyn TagSource, TagHash) -> crate::Result<Vec<(FieldPath, TagReference)>>;
+
+/// Reference to another tag, found while visiting a value
+#[cfg(feature = "tiger_pkg")]
+#[derive(Clone, Copy)]
+pub struct TagReference {
+    pub hash: TagReferenceHash,
+    /// Name of the referenced type, for typed references such as [`Tag<T>`](crate::Tag)
+    pub type_name: Option<&'static str>,
+    /// Reads the referenced tag, for typed references
+    pub follow: Option<FollowTagFn>,
+}
+
+#[cfg(feature = "tiger_pkg")]
+impl std::fmt::Debug for TagReference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TagReference")
+            .field("hash", &self.hash)
+            .field("type_name", &self.type_name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Collects all tag references in `value`, along with their paths
+#[cfg(feature = "tiger_pkg")]
+pub fn collect_tag_references<T: TigerVisit>(value: &T) -> Vec<(FieldPath, TagReference)> {
+    struct Collector(Vec<(FieldPath, TagReference)>);
+
+    impl Visitor for Collector {
+        fn visit_tag(&mut self, path: &FieldPath, reference: &TagReference) {
+            self.0.push((path.clone(), *reference));
+        }
+    }
+
+    let mut collector = Collector(vec![]);
+    walk(value, &mut collector);
+    collector.0
+}
+
+#[cfg(feature = "tiger_pkg")]
+fn follow_tag<T: TigerReadable + TigerVisit>(
+    source: &dyn TagSource,
+    tag: TagHash,
+) -> crate::Result<Vec<(FieldPath, TagReference)>> {
+    use crate::PackageManagerExt;
+
+    let value: T = source.read_tag_struct(tag)?;
+    Ok(collect_tag_references(&value))
+}
+
+/// Reference found in a tag, see [`DependencyGraph`]
+#[cfg(feature = "tiger_pkg")]
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    /// Path of the referencing field within the parent tag
+    pub path: FieldPath,
+    pub reference: TagReference,
+    /// Tag the reference resolves to, `None` for 64-bit hashes that could not be resolved
+    pub target: Option<TagHash>,
+}
+
+/// Tags referenced by a root tag, recursively.
+/// See [`PackageManagerExt::dependency_graph`](crate::PackageManagerExt::dependency_graph)
+#[cfg(feature = "tiger_pkg")]
+#[derive(Debug)]
+pub struct DependencyGraph {
+    pub root: TagHash,
+    /// References of every tag that was read, keyed by the referencing tag
+    pub nodes: HashMap<TagHash, Vec<Dependency>>,
+    /// Referenced tags that failed to read
+    pub errors: Vec<(TagHash, crate::Error)>,
+}
+
+#[cfg(feature = "tiger_pkg")]
+impl DependencyGraph {
+    /// Builds the graph from the references of the root tag, reading every tag referenced through a typed reference
+    pub(crate) fn build(
+        source: &dyn TagSource,
+        root: TagHash,
+        references: Vec<(FieldPath, TagReference)>,
+    ) -> Self {
+        let mut graph = DependencyGraph {
+            root,
+            nodes: HashMap::new(),
+            errors: vec![],
+        };
+
+        let mut seen = HashSet::from([root]);
+        let mut pending = VecDeque::from([(root, references)]);
+        while let Some((tag, references)) = pending.pop_front() {
+            let mut dependencies = Vec::with_capacity(references.len());
+            for (path, reference) in references {
+                let target = match reference.hash {
+                    TagReferenceHash::Tag(tag) => Some(tag),
+                    TagReferenceHash::Tag64(hash) => source.resolve_tag64(hash),
+                };
+
+                if let (Some(target), Some(follow)) = (target, reference.follow) {
+                    if seen.insert(target) {
+                        match follow(source, target) {
+                            Ok(references) => pending.push_back((target, references)),
+                            Err(e) => graph.errors.push((target, e)),
+                        }
+                    }
+                }
+
+                dependencies.push(Dependency {
+                    path,
+                    reference,
+                    target,
+                });
+            }
+
+            graph.nodes.insert(tag, dependencies);
+        }
+
+        graph
+    }
+
+    /// Returns the direct references of `tag`
+    pub fn dependencies(&self, tag: TagHash) -> &[Dependency] {
+        self.nodes.get(&tag).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns every resolved tag in the graph, including the root and tags that were not read, sorted by hash
+    pub fn tags(&self) -> Vec<TagHash> {
+        let mut tags: Vec<TagHash> = self
+            .nodes
+            .values()
+            .flatten()
+            .filter_map(|d| d.target)
+            .chain(std::iter::once(self.root))
+            .collect();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+}
+
+#[cfg(feature = "tiger_pkg")]
+impl TigerVisit for TagHash {
+    fn visit_fields(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        if self.is_some() {
+            visitor.visit_tag(
+                path,
+                &TagReference {
+                    hash: TagReferenceHash::Tag(*self),
+                    type_name: None,
+                    follow: None,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(feature = "tiger_pkg")]
+impl TigerVisit for TagHash64 {
+    fn visit_fields(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        if self.0 != 0 && self.0 != u64::MAX {
+            visitor.visit_tag(
+                path,
+                &TagReference {
+                    hash: TagReferenceHash::Tag64(*self),
+                    type_name: None,
+                    follow: None,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(feature = "tiger_pkg")]
+impl<T: TigerReadable + TigerVisit> TigerVisit for crate::Tag<T> {
+    fn visit_fields(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        if self.is_some() {
+            visitor.visit_tag(
+                path,
+                &TagReference {
+                    hash: TagReferenceHash::Tag(self.hash()),
+                    type_name: Some(std::any::type_name::<T>()),
+                    follow: Some(follow_tag::<T>),
+                },
+            );
+        }
+    }
+}
+
+#[cfg(feature = "tiger_pkg")]
+impl<T: TigerReadable + TigerVisit> TigerVisit for crate::Tag64<T> {
+    fn visit_fields(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+        if self.is_some() {
+            visitor.visit_tag(
+                path,
+                &TagReference {
+                    hash: TagReferenceHash::Tag64(self.hash()),
+                    type_name: Some(std::any::type_name::<T>()),
+                    follow: Some(follow_tag::<T>),
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+
+    use super::{walk, FieldPath, PathSegment, TigerVisit, Visitor};
+
+    struct Node {
+        value: u32,
+        children: Vec<(u8, Box<u16>)>,
+    }
+
+    impl TigerVisit for Node {
+        fn visit_fields(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+            super::visit_child(&self.value, PathSegment::Field("value"), path, visitor);
+            super::visit_child(
+                &self.children,
+                PathSegment::Field("children"),
+                path,
+                visitor,
+            );
+        }
+    }
+
+    #[test]
+    fn test_visit() {
+        let node = Node {
+            value: 1,
+            children: vec![(2, Box::new(3)), (4, Box::new(5))],
+        };
+
+        let mut visited = vec![];
+        walk(&node, &mut |path: &FieldPath, value: &dyn Any| {
+            if let Some(v) = value.downcast_ref::<u16>() {
+                visited.push((path.to_string(), *v));
+            }
+        });
+
+        assert_eq!(
+            visited,
+            [
+                ("children[0][1]".to_string(), 3),
+                ("children[1][1]".to_string(), 5)
+            ]
+        );
+
+        // Wrappers are transparent
+        let mut count = 0;
+        walk(
+            &Box::new(Some(7u32)),
+            &mut |path: &FieldPath, value: &dyn Any| {
+                assert!(path.is_empty());
+                assert_eq!(value.downcast_ref::<u32>(), Some(&7));
+                count += 1;
+            },
+        );
+        assert_eq!(count, 1);
+    }
+
+    #[cfg(feature = "tiger_pkg")]
+    #[test]
+    fn test_dependency_graph() -> crate::Result<()> {
+        use tiger_pkg::{TagHash, TagHash64};
+
+        use crate::{
+            source::{MemoryTagSource, TagEntry},
+            Endian, PackageManagerExt, Tag64, TigerReadable, TigerReader,
+        };
+
+        struct TestTag {
+            children: [Tag64<TestTag>; 2],
+            texture: TagHash,
+        }
+
+        impl TigerReadable for TestTag {
+            fn read_ds_endian(reader: &mut dyn TigerReader, endian: Endian) -> crate::Result<Self> {
+                Ok(TestTag {
+                    children: TigerReadable::read_ds_endian(reader, endian)?,
+                    texture: TigerReadable::read_ds_endian(reader, endian)?,
+                })
+            }
+
+            const ID: Option<u32> = Some(0x80801234);
+            const SIZE: usize = 20;
+        }
+
+        impl TigerVisit for TestTag {
+            fn visit_fields(&self, path: &mut FieldPath, visitor: &mut dyn Visitor) {
+                super::visit_child(
+                    &self.children,
+                    PathSegment::Field("children"),
+                    path,
+                    visitor,
+                );
+                super::visit_child(&self.texture, PathSegment::Field("texture"), path, visitor);
+            }
+        }
+
+        fn tag_data(children: [u64; 2], texture: TagHash) -> Vec<u8> {
+            [
+                &children[0].to_le_bytes()[..],
+                &children[1].to_le_bytes(),
+                &texture.0.to_le_bytes(),
+            ]
+            .concat()
+        }
+
+        let entry = TagEntry {
+            reference: 0x80801234,
+            file_type: 8,
+            file_subtype: 0,
+        };
+        let root = TagHash::new(1, 0);
+        let child = TagHash::new(1, 1);
+        let broken = TagHash::new(1, 2);
+        let texture = TagHash::new(2, 0);
+
+        let mut source = MemoryTagSource::new(Endian::Little);
+        // The child references the root again, which must not be read twice
+        source.insert(root, entry, tag_data([0x10, 0x20], texture));
+        source.insert(child, entry, tag_data([0x30, u64::MAX], TagHash::NONE));
+        source.insert(broken, entry, [0u8; 4]);
+        source.insert_tag64(TagHash64(0x10), child);
+        source.insert_tag64(TagHash64(0x20), broken);
+        source.insert_tag64(TagHash64(0x30), root);
+
+        let graph = source.dependency_graph::<TestTag>(root)?;
+        let paths: Vec<String> = graph
+            .dependencies(root)
+            .iter()
+            .map(|d| d.path.to_string())
+            .collect();
+        assert_eq!(paths, ["children[0]", "children[1]", "texture"]);
+        assert_eq!(graph.dependencies(child).len(), 1);
+        assert_eq!(graph.dependencies(child)[0].target, Some(root));
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.errors.len(), 1);
+        assert_eq!(graph.errors[0].0, broken);
+        assert_eq!(graph.tags(), [root, child, broken, texture]);
+
+        Ok(())
+    }
+}
@@ -1,26 +1,169 @@
-use proc_macro2::Ident;
+use darling::{ast::NestedMeta, FromMeta, FromVariant};
+use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 
 use crate::ast::Repr;
 
-pub fn generate(enumm: syn::ItemEnum) -> proc_macro::TokenStream {
-    let ident = enumm.ident.clone();
+#[derive(FromMeta, Default, Debug)]
+#[darling(default)]
+struct Opts {
+    /// Return an error for unknown discriminants, even if the enum has an unknown variant
+    strict: bool,
+}
+
+#[derive(FromVariant, Default, Debug)]
+#[darling(default, attributes(tiger))]
+struct OptsVariant {
+    /// Catch-all variant for unknown discriminants, holding the raw value
+    unknown: bool,
+}
+
+pub fn generate(
+    attr: proc_macro::TokenStream,
+    mut enumm: syn::ItemEnum,
+) -> proc_macro::TokenStream {
+    let args = NestedMeta::parse_meta_list(attr.into()).unwrap();
+    let opts = match <Opts as darling::FromMeta>::from_list(&args) {
+        Ok(x) => x,
+        Err(e) => return e.write_errors().into(),
+    };
 
-    let enum_idents: Vec<Ident> = enumm.variants.iter().map(|v| v.ident.clone()).collect();
+    let ident = enumm.ident.clone();
 
     let repr = Repr::from_attributes(&enumm.attrs).unwrap();
     let repr_type = repr.ident;
 
+    let mut enum_idents: Vec<Ident> = vec![];
+    let mut enum_discriminants: Vec<TokenStream> = vec![];
+    let mut unknown_ident: Option<Ident> = None;
+    let mut last_discriminant: Option<TokenStream> = None;
+    for v in enumm.variants.iter() {
+        let d = match OptsVariant::from_variant(v) {
+            Ok(x) => x,
+            Err(e) => return e.write_errors().into(),
+        };
+
+        // Fields make `Self::Variant as repr` unavailable, so discriminants are evaluated the same way rustc assigns them
+        let discriminant = if let Some((_, expr)) = &v.discriminant {
+            quote!((#expr))
+        } else if let Some(last) = &last_discriminant {
+            quote!((#last + 1))
+        } else {
+            quote!(0)
+        };
+        last_discriminant = Some(discriminant.clone());
+
+        if d.unknown {
+            if unknown_ident.is_some() {
+                return quote! {
+                    compile_error!("Only one variant can be marked as #[tiger(unknown)]");
+                }
+                .into();
+            }
+
+            if !matches!(&v.fields, syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1) {
+                return quote! {
+                    compile_error!("The #[tiger(unknown)] variant must have a single field holding the raw value");
+                }
+                .into();
+            }
+
+            unknown_ident = Some(v.ident.clone());
+            continue;
+        }
+
+        if !v.fields.is_empty() {
+            return quote! {
+                compile_error!("Only the #[tiger(unknown)] variant can have fields");
+            }
+            .into();
+        }
+
+        enum_idents.push(v.ident.clone());
+        enum_discriminants.push(discriminant);
+    }
+
     let impl_struct_size = quote! {
         const SIZE: usize = <#repr_type as ::tiger_parse::TigerReadable>::SIZE;
     };
 
+    let unknown_arm = match &unknown_ident {
+        Some(unknown_ident) if !opts.strict => quote! {
+            _ => Ok(#ident::#unknown_ident(value)),
+        },
+        _ => quote! {
+            _ => Err(::tiger_parse::error::Error::EnumVariantOutOfRange {
+                typename: ::tiger_parse::ShortName::of::<Self>().to_string(),
+                value: format!("{value} (0x{value:X})"),
+                repr: stringify!(#repr_type),
+                valid: vec![#((#enum_discriminants as #repr_type).to_string()),*],
+                // Only looked up on error, the value has already been read
+                offset: reader
+                    .stream_position()
+                    .unwrap_or_default()
+                    .saturating_sub(<#repr_type as ::tiger_parse::TigerReadable>::SIZE as u64),
+            }),
+        },
+    };
+
     let variant_match = quote! {
         let value = <#repr_type as ::tiger_parse::TigerReadable>::read_ds_endian(reader, endian)?;
         match value {
-            #(x if x == (Self::#enum_idents as #repr_type) => Ok(#ident::#enum_idents),)*
-            _ => Err(::tiger_parse::error::Error::EnumVariantOutOfRange(value as usize)),
+            #(x if x == #enum_discriminants as #repr_type => Ok(#ident::#enum_idents),)*
+            #unknown_arm
+        }
+    };
+
+    let diff_impl = if cfg!(feature = "diff") {
+        let unknown_value = unknown_ident.as_ref().map(|unknown_ident| {
+            quote! {
+                #ident::#unknown_ident(value) => (*value, format!("{value} (0x{value:X})")),
+            }
+        });
+
+        quote! {
+            impl ::tiger_parse::diff::TigerDiff for #ident {
+                fn diff(&self, new: &Self, ctx: &mut ::tiger_parse::diff::DiffContext) {
+                    let value = |v: &Self| -> (#repr_type, String) {
+                        match v {
+                            #(#ident::#enum_idents => (#enum_discriminants as #repr_type, stringify!(#enum_idents).to_string()),)*
+                            #unknown_value
+                        }
+                    };
+
+                    let (old, new) = (value(self), value(new));
+                    if old.0 != new.0 {
+                        ctx.push(::tiger_parse::diff::Change::Value { old: old.1, new: new.1 });
+                    }
+                }
+            }
         }
+    } else {
+        quote! {}
+    };
+
+    // Strip the tiger attribute from all variants
+    for v in enumm.variants.iter_mut() {
+        v.attrs.retain(|v| !v.meta.path().is_ident("tiger"));
+    }
+
+    let visit_impl = if cfg!(feature = "visit") {
+        quote! {
+            impl ::tiger_parse::visit::TigerVisit for #ident {}
+        }
+    } else {
+        quote! {}
+    };
+
+    let reflect_impl = if cfg!(feature = "reflect") {
+        quote! {
+            impl ::tiger_parse::reflect::ReflectType for #ident {
+                const REFLECTED_TYPE: ::tiger_parse::reflect::ReflectedType =
+                    ::tiger_parse::reflect::ReflectedType::Other(std::borrow::Cow::Borrowed(stringify!(#ident)));
+            }
+        }
+    } else {
+        quote! {}
     };
 
     quote! {
@@ -34,5 +177,11 @@ pub fn generate(enumm: syn::ItemEnum) -> proc_macro::TokenStream {
             const ID: Option<u32> = None;
             #impl_struct_size
         }
+
+        #reflect_impl
+
+        #visit_impl
+
+        #diff_impl
     }.into()
 }
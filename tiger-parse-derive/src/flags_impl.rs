@@ -1,22 +1,149 @@
-use quote::quote;
+use darling::{FromDeriveInput, FromMeta};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+#[derive(Debug, Clone, Copy, Default, FromMeta)]
+enum FlagsMode {
+    /// Silently drop bits that don't correspond to a known flag
+    #[default]
+    #[darling(rename = "truncate")]
+    Truncate,
+
+    /// Return an error if any unknown bits are set
+    #[darling(rename = "strict")]
+    Strict,
+
+    /// Keep unknown bits as-is
+    #[darling(rename = "retain")]
+    Retain,
+}
+
+#[derive(FromDeriveInput, Debug)]
+#[darling(attributes(tiger))]
+struct Opts {
+    ident: syn::Ident,
+
+    #[darling(default)]
+    mode: FlagsMode,
+}
 
 pub fn generate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast: proc_macro2::TokenStream = input.into();
-    let item: syn::ItemStruct = syn::parse2(ast).expect("Failed to parse item as struct");
+    let item: syn::DeriveInput = syn::parse2(ast).expect("Failed to parse item as struct");
+    let opts = match Opts::from_derive_input(&item) {
+        Ok(x) => x,
+        Err(e) => return e.write_errors().into(),
+    };
+
+    let ident = &opts.ident;
+    let from_bits = match opts.mode {
+        FlagsMode::Truncate => quote! {
+            Ok(<Self as ::tiger_parse::bitflags::Flags>::from_bits_truncate(bits))
+        },
+        FlagsMode::Strict => quote! {
+            <Self as ::tiger_parse::bitflags::Flags>::from_bits(bits).ok_or_else(|| {
+                ::tiger_parse::error::Error::UnknownFlagBits {
+                    typename: ::tiger_parse::ShortName::of::<Self>().to_string(),
+                    bits: format!(
+                        "0x{:X}",
+                        bits & !::tiger_parse::bitflags::Flags::bits(&<Self as ::tiger_parse::bitflags::Flags>::all())
+                    ),
+                    // Only looked up on error, the bits have already been read
+                    offset: reader
+                        .stream_position()
+                        .unwrap_or_default()
+                        .saturating_sub(<Self as ::tiger_parse::TigerReadable>::SIZE as u64),
+                }
+            })
+        },
+        FlagsMode::Retain => quote! {
+            Ok(<Self as ::tiger_parse::bitflags::Flags>::from_bits_retain(bits))
+        },
+    };
+
+    let mut reflected_flags_stream = TokenStream::new();
+    if cfg!(feature = "reflect") {
+        let reflected_flags_ident = format_ident!("_{}_REFLECT", ident);
+        reflected_flags_stream.extend(quote! {
+            #[allow(non_upper_case_globals)]
+            #[::tiger_parse::distributed_slice(crate::FLAGS)]
+            static #reflected_flags_ident: ::tiger_parse::reflect::ReflectedFlags = {
+                const FLAGS: &[::tiger_parse::bitflags::Flag<#ident>] = <#ident as ::tiger_parse::bitflags::Flags>::FLAGS;
+                const REFLECTED: [::tiger_parse::reflect::ReflectedFlag; FLAGS.len()] = {
+                    let mut reflected = [::tiger_parse::reflect::ReflectedFlag { name: "", value: 0 }; FLAGS.len()];
+                    let mut i = 0;
+                    while i < FLAGS.len() {
+                        reflected[i] = ::tiger_parse::reflect::ReflectedFlag {
+                            name: FLAGS[i].name(),
+                            value: FLAGS[i].value().bits() as u128,
+                        };
+                        i += 1;
+                    }
+                    reflected
+                };
+
+                ::tiger_parse::reflect::ReflectedFlags {
+                    name: std::borrow::Cow::Borrowed(stringify!(#ident)),
+                    size: <#ident as ::tiger_parse::TigerReadable>::SIZE,
+                    flags: std::borrow::Cow::Borrowed(&REFLECTED),
+                }
+            };
+
+            impl ::tiger_parse::reflect::ReflectType for #ident {
+                const REFLECTED_TYPE: ::tiger_parse::reflect::ReflectedType =
+                    ::tiger_parse::reflect::ReflectedType::Other(std::borrow::Cow::Borrowed(stringify!(#ident)));
+            }
+        });
+    }
+
+    let visit_impl = if cfg!(feature = "visit") {
+        quote! {
+            impl ::tiger_parse::visit::TigerVisit for #ident {}
+        }
+    } else {
+        quote! {}
+    };
+
+    let diff_impl = if cfg!(feature = "diff") {
+        quote! {
+            impl ::tiger_parse::diff::TigerDiff for #ident {
+                fn diff(&self, new: &Self, ctx: &mut ::tiger_parse::diff::DiffContext) {
+                    let (old, new) = (
+                        ::tiger_parse::bitflags::Flags::bits(self),
+                        ::tiger_parse::bitflags::Flags::bits(new),
+                    );
+                    if old != new {
+                        ctx.push(::tiger_parse::diff::Change::Value {
+                            old: format!("{old:#X}"),
+                            new: format!("{new:#X}"),
+                        });
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
 
-    let ident = &item.ident;
     quote! {
         impl ::tiger_parse::TigerReadable for #ident {
             fn read_ds_endian(
-                reader: &mut dyn TigerReader,
+                reader: &mut dyn ::tiger_parse::TigerReader,
                 endian: ::tiger_parse::Endian,
             ) -> ::tiger_parse::Result<Self> {
-                let bits: <Self as bitflags::Flags>::Bits = ::tiger_parse::TigerReadable::read_ds_endian(reader, endian)?;
-                Ok(<Self as bitflags::Flags>::from_bits_truncate(bits))
+                let bits: <Self as ::tiger_parse::bitflags::Flags>::Bits = ::tiger_parse::TigerReadable::read_ds_endian(reader, endian)?;
+                #from_bits
             }
 
             const ID: Option<u32> = None;
-            const SIZE: usize = <Self as bitflags::Flags>::Bits::SIZE;
+            const SIZE: usize = <<Self as ::tiger_parse::bitflags::Flags>::Bits as ::tiger_parse::TigerReadable>::SIZE;
         }
-    }.into()
+
+        #visit_impl
+
+        #diff_impl
+
+        #reflected_flags_stream
+    }
+    .into()
 }
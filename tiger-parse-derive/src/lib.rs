@@ -4,6 +4,7 @@ mod ast;
 mod enum_impl;
 mod flags_impl;
 mod struct_impl;
+mod variant_impl;
 
 #[proc_macro_attribute]
 pub fn tiger_type(
@@ -36,7 +37,7 @@ pub fn tiger_type(
                 variants: data_enum.variants.clone(),
             };
 
-            enum_impl::generate(enumm)
+            enum_impl::generate(attr, enumm)
         }
         syn::Data::Union(_) => quote! {
             compile_error!("Unions are not supported");
@@ -45,7 +46,17 @@ pub fn tiger_type(
     }
 }
 
-#[proc_macro_derive(TigerFlags)]
+/// Implements `VariantEnum` for an enum of resource classes, dispatching on the class ID of each variant's type
+#[proc_macro_attribute]
+pub fn tiger_variant(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let enumm = syn::parse_macro_input!(item as syn::ItemEnum);
+    variant_impl::generate(attr, enumm)
+}
+
+#[proc_macro_derive(TigerFlags, attributes(tiger))]
 pub fn tiger_flags(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     flags_impl::generate(input)
 }
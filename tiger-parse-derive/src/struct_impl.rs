@@ -32,6 +32,14 @@ struct Opts {
 
     #[darling(rename = "size")]
     struct_size: Option<usize>,
+
+    /// Implements `TigerVisit` with the `visit` feature, which requires it on every visited field type
+    #[darling(default)]
+    visit: bool,
+
+    /// Implements `TigerDiff` with the `diff` feature, which requires it on every compared field type
+    #[darling(default)]
+    diff: bool,
 }
 
 #[derive(FromField, Default, Debug)]
@@ -43,6 +51,39 @@ struct OptsField {
     field_type: FieldType,
 
     debug: bool,
+
+    /// Only read the field if the expression evaluates to true. The field must be an `Option<T>`,
+    /// and the expression can reference any field declared before it.
+    /// Absent fields aren't stored at all, which makes the size of the struct vary unless `reserve` is set.
+    /// Written as `#[tiger(if = "...")]`, see [`rename_keyword_options`]
+    condition: Option<syn::Expr>,
+
+    /// Absent conditional fields still take up the size of `T` in the layout, and are skipped over
+    reserve: bool,
+
+    /// Reads a `Vec<T>` field as `count` elements stored inline, instead of a relative array block.
+    /// The expression can reference any field declared before it.
+    count: Option<syn::Expr>,
+
+    /// Reads the field with a custom function instead of its `TigerReadable` implementation.
+    /// The function takes the reader and endian, and returns `tiger_parse::Result<T>`.
+    read_with: Option<syn::Path>,
+
+    /// Reads the raw parameter type of the given `fn(Raw) -> T` and maps it to the field type
+    map: Option<syn::Path>,
+
+    /// Size of the field in the struct layout, for fields read with `read_with`.
+    /// Required when the field type doesn't implement `TigerReadable`.
+    size: Option<usize>,
+
+    /// Marks a tag field as the data tag of a header tag, implementing `DataTagHeader` for the struct
+    data_tag: bool,
+
+    /// Leaves the field out of the `TigerVisit` implementation. Fields read with `read_with` or `map` are always left out.
+    skip_visit: bool,
+
+    /// Leaves the field out of the `TigerDiff` implementation. Fields read with `read_with` or `map` are always left out.
+    skip_diff: bool,
 }
 
 pub fn generate(
@@ -57,6 +98,19 @@ pub fn generate(
 
     let ident = struc.ident.clone();
 
+    // Every type parameter has to be readable for the struct to be readable
+    let mut generics = struc.generics.clone();
+    let is_generic =
+        generics.type_params().next().is_some() || generics.const_params().next().is_some();
+    let type_params: Vec<Ident> = generics.type_params().map(|p| p.ident.clone()).collect();
+    for type_param in type_params {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(#type_param: ::tiger_parse::TigerReadable));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     let struct_id = opts.struct_id;
     let struct_id_or_zero = struct_id.unwrap_or(0);
     let impl_struct_id = if let Some(struct_id) = struct_id {
@@ -67,22 +121,71 @@ pub fn generate(
         quote! {}
     };
 
-    if opts.struct_subtype.is_some() && opts.struct_type.is_none() {
+    // Additional accepted entry types are declared by repeating the attribute,
+    // eg. `#[tiger_type(etype = 8, esubtype = 1)]`, which is then stripped from the item
+    let mut etypes = vec![(opts.struct_type, opts.struct_subtype)];
+    let mut extra_attrs = vec![];
+    struc.attrs.retain(|attr| {
+        let is_tiger_type = attr
+            .path()
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "tiger_type");
+        if is_tiger_type {
+            extra_attrs.push(attr.clone());
+        }
+
+        !is_tiger_type
+    });
+
+    for attr in extra_attrs {
+        let extra = match Opts::from_meta(&attr.meta) {
+            Ok(x) => x,
+            Err(e) => return e.write_errors().into(),
+        };
+
+        if extra.struct_id.is_some()
+            || extra.struct_size.is_some()
+            || extra.visit
+            || extra.diff
+            || extra.struct_type.is_none()
+        {
+            return quote! {
+                compile_error!("Repeated #[tiger_type] attributes may only specify `etype` and `esubtype`");
+            }
+            .into();
+        }
+
+        etypes.push((extra.struct_type, extra.struct_subtype));
+    }
+
+    if etypes
+        .iter()
+        .any(|(etype, esubtype)| esubtype.is_some() && etype.is_none())
+    {
         return quote! {
             compile_error!("If subtype is defined, type must be defined as well");
         }
         .into();
     }
 
-    let impl_struct_type = if let (Some(st), sbt) = (opts.struct_type, opts.struct_subtype) {
-        let sbt = if let Some(sbt) = sbt {
-            quote! { Some(#sbt) }
-        } else {
-            quote! { None }
-        };
+    let etypes: Vec<TokenStream> = etypes
+        .into_iter()
+        .filter_map(|(etype, esubtype)| {
+            let esubtype = if let Some(esubtype) = esubtype {
+                quote! { Some(#esubtype) }
+            } else {
+                quote! { None }
+            };
+
+            etype.map(|etype| quote!((#etype, #esubtype)))
+        })
+        .collect();
 
+    let impl_struct_type = if let Some(first) = etypes.first() {
         quote! {
-            const ETYPE: Option<(u8, Option<u8>)> = Some((#st, #sbt));
+            const ETYPE: Option<(u8, Option<u8>)> = Some(#first);
+            const ETYPES: &'static [(u8, Option<u8>)] = &[#(#etypes),*];
         }
     } else {
         quote! {}
@@ -94,13 +197,44 @@ pub fn generate(
         0
     });
 
+    // Whether any field has a size that depends on the data, see `TigerReadable::VARIABLE_SIZE`
+    let mut variable_size = quote!(false);
+    let mut uses_counts = false;
+    let mut uses_unreserved_conditions = false;
+
+    for f in struc.fields.iter_mut() {
+        rename_keyword_options(f);
+    }
+
     let mut last_offset = 0u64;
     let mut fieldstream = TokenStream::new();
     let mut fieldstream_assign = TokenStream::new();
     let mut uses_offsets = false;
     let mut is_tuple = false;
+    let mut data_tag_field: Option<syn::Member> = None;
+    let mut visit_stream = TokenStream::new();
+    let mut visit_mut_stream = TokenStream::new();
+    let mut diff_stream = TokenStream::new();
     for (i, f) in struc.fields.iter_mut().enumerate() {
         let d = OptsField::from_field(f).expect("Invalid field options");
+        let Some(field_size) = field_size(&f.ty, &d) else {
+            return quote! {
+                compile_error!("Conditional fields must be of type Option<T>");
+            }
+            .into();
+        };
+
+        if [d.count.is_some(), d.read_with.is_some(), d.map.is_some()]
+            .iter()
+            .filter(|&&v| v)
+            .count()
+            > 1
+        {
+            return quote! {
+                compile_error!("Only one of `count`, `read_with` and `map` can be used on a field");
+            }
+            .into();
+        }
 
         let (fident, display_ident) = if let Some(fident) = f.ident.clone() {
             (fident.clone(), fident.to_string())
@@ -112,6 +246,53 @@ pub fn generate(
             )
         };
 
+        let member = match &f.ident {
+            Some(fident) => syn::Member::Named(fident.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(i)),
+        };
+
+        // Custom readers are used for types that don't implement the rest of the traits either
+        let custom_read = d.read_with.is_some() || d.map.is_some();
+        if !d.skip_visit && !custom_read {
+            visit_stream.extend(quote! {
+                ::tiger_parse::visit::visit_child(
+                    &self.#member,
+                    ::tiger_parse::visit::PathSegment::Field(#display_ident),
+                    path,
+                    visitor,
+                );
+            });
+            visit_mut_stream.extend(quote! {
+                ::tiger_parse::visit::visit_child_mut(
+                    &mut self.#member,
+                    ::tiger_parse::visit::PathSegment::Field(#display_ident),
+                    path,
+                    visitor,
+                );
+            });
+        }
+
+        if !d.skip_diff && !custom_read {
+            diff_stream.extend(quote! {
+                ctx.child(
+                    ::tiger_parse::visit::PathSegment::Field(#display_ident),
+                    &self.#member,
+                    &new.#member,
+                );
+            });
+        }
+
+        if d.data_tag {
+            if data_tag_field.is_some() {
+                return quote! {
+                    compile_error!("Only one field can be marked as #[tiger(data_tag)]");
+                }
+                .into();
+            }
+
+            data_tag_field = Some(member);
+        }
+
         let ftype = f.ty.clone();
         if let Some(field_offset) = d.field_offset {
             if field_offset >= last_offset {
@@ -140,14 +321,86 @@ pub fn generate(
             });
         }
 
-        fieldstream.extend(quote! {
-            let #fident = <_>::read_ds_endian(reader, endian).with_field(&tiger_parse::ShortName::of::<Self>().to_string(), #display_ident)?;
-        });
+        let mut read_field = quote! {
+            <_>::read_ds_endian(reader, endian).with_field(&tiger_parse::ShortName::of::<Self>().to_string(), #display_ident)?
+        };
 
-        if d.debug {
+        if let Some(count) = &d.count {
+            read_field = quote! {
+                ::tiger_parse::read_inline_vec(reader, endian, (#count) as usize).with_field(&tiger_parse::ShortName::of::<Self>().to_string(), #display_ident)?
+            };
+        }
+
+        if let Some(read_with) = &d.read_with {
+            read_field = quote! {
+                #read_with(reader, endian).with_field(&tiger_parse::ShortName::of::<Self>().to_string(), #display_ident)?
+            };
+        }
+
+        if let Some(map) = &d.map {
+            read_field = quote! {
+                ::tiger_parse::read_mapped(reader, endian, #map).with_field(&tiger_parse::ShortName::of::<Self>().to_string(), #display_ident)?
+            };
+        }
+
+        if let Some(condition) = &d.condition {
+            let skip_absent = if d.reserve {
+                quote! {
+                    reader.seek(::std::io::SeekFrom::Current((#field_size) as i64))?;
+                }
+            } else {
+                uses_unreserved_conditions = true;
+                quote! {}
+            };
+
+            read_field = quote! {
+                if #condition {
+                    Some(#read_field)
+                } else {
+                    #skip_absent
+                    None
+                }
+            };
+        }
+
+        if cfg!(feature = "tracing") {
+            fieldstream.extend(quote! {
+                let #fident = {
+                    let span = ::tiger_parse::tracing::trace_span!(
+                        "tiger_field",
+                        field = #display_ident,
+                        r#type = %::tiger_parse::ShortName::of::<#ftype>(),
+                        offset = ::tiger_parse::tracing::field::Empty,
+                    );
+                    if !span.is_disabled() {
+                        span.record("offset", reader.stream_position()?);
+                    }
+                    let _guard = span.enter();
+                    #read_field
+                };
+            });
+        } else {
             fieldstream.extend(quote! {
+                let #fident = #read_field;
+            });
+        }
+
+        if d.debug {
+            if cfg!(feature = "tracing") {
+                fieldstream.extend(quote! {
+                    ::tiger_parse::tracing::debug!(
+                        r#type = %::tiger_parse::ShortName::of::<Self>(),
+                        field = #display_ident,
+                        offset,
+                        "{:#X?}",
+                        #fident
+                    );
+                });
+            } else {
+                fieldstream.extend(quote! {
                     eprintln!("[{}.{} @ 0x{:X}]: {:#X?}", tiger_parse::ShortName::of::<Self>(), stringify!(#fident), offset, #fident);
                 });
+            }
         }
 
         fieldstream_assign.extend(quote! {
@@ -155,13 +408,48 @@ pub fn generate(
         });
 
         fieldstream_size.extend(quote! {
-            + <#ftype as ::tiger_parse::TigerReadable>::SIZE
+            + #field_size
         });
+
+        // Fields with a custom reader don't need to implement `TigerReadable`, their size is trusted as-is
+        if d.count.is_some() {
+            uses_counts = true;
+        } else if d.map.is_none() && d.read_with.is_none() {
+            let value_type = field_value_type(&f.ty, &d).expect("Invalid conditional field");
+            variable_size.extend(quote! {
+                || <#value_type as ::tiger_parse::TigerReadable>::VARIABLE_SIZE
+            });
+        }
+    }
+
+    if uses_counts || uses_unreserved_conditions {
+        variable_size = quote!(true);
     }
 
     let impl_struct_size = if let Some(defined_size) = opts.struct_size {
+        if uses_counts {
+            return quote! {
+                compile_error!("Structs with inline counted arrays can't define a size");
+            }
+            .into();
+        }
+
+        if uses_unreserved_conditions {
+            return quote! {
+                compile_error!("Structs with conditional fields can't define a size, unless the fields are declared with `reserve`");
+            }
+            .into();
+        }
+
         quote! {
-            const SIZE: usize = #defined_size;
+            const SIZE: usize = {
+                assert!(
+                    !<Self as ::tiger_parse::TigerReadable>::VARIABLE_SIZE,
+                    "Structs with variable-size fields can't define a size"
+                );
+                #defined_size
+            };
+            const VARIABLE_SIZE: bool = #variable_size;
         }
     } else {
         if uses_offsets {
@@ -173,6 +461,7 @@ pub fn generate(
 
         quote! {
             const SIZE: usize = #fieldstream_size;
+            const VARIABLE_SIZE: bool = #variable_size;
         }
     };
 
@@ -190,6 +479,11 @@ pub fn generate(
 
     let mut reflected_struct_stream = TokenStream::new();
     if cfg!(feature = "reflect") {
+        let type_params: Vec<Ident> = struc
+            .generics
+            .type_params()
+            .map(|p| p.ident.clone())
+            .collect();
         let mut struct_reflect_field_stream = TokenStream::new();
         let mut struct_reflect_field_offset_stream = TokenStream::new();
         struct_reflect_field_offset_stream.extend(quote! {
@@ -210,38 +504,93 @@ pub fn generate(
                 };
             }
 
-            let ftype = f.ty.clone();
-            let type_reflect = type_to_reflect(&f.ty);
+            let field_size = field_size(&f.ty, &d).expect("Invalid conditional field");
+            let type_reflect = type_to_reflect(field_value_type(&f.ty, &d).unwrap(), &type_params);
+            let condition = reflect_expression(d.condition.as_ref());
+            let count = reflect_expression(d.count.as_ref());
+            let reserve = d.reserve;
+            let variable_size = if d.count.is_some() || (d.condition.is_some() && !d.reserve) {
+                quote!(true)
+            } else if d.map.is_some() || d.read_with.is_some() {
+                quote!(false)
+            } else {
+                let value_type = field_value_type(&f.ty, &d).unwrap();
+                quote!(<#value_type as ::tiger_parse::TigerReadable>::VARIABLE_SIZE)
+            };
 
             struct_reflect_field_stream.extend(quote! {
                 ::tiger_parse::reflect::ReflectedField {
                     name: std::borrow::Cow::Borrowed(#fident),
-                    size: <#ftype as ::tiger_parse::TigerReadable>::SIZE,
+                    size: #field_size,
                     offset: #struct_reflect_field_offset_stream,
                     explicit_offset: #explicit_offset,
+                    condition: #condition,
+                    reserve: #reserve,
+                    count: #count,
+                    variable_size: #variable_size,
                     ty: #type_reflect,
                 },
             });
 
             struct_reflect_field_offset_stream.extend(quote! {
-                + <#ftype as ::tiger_parse::TigerReadable>::SIZE
+                + #field_size
             });
         }
 
-        let reflected_struct_ident = format_ident!("_{}_REFLECT", ident);
         reflected_struct_stream.extend(quote! {
-            #[allow(non_upper_case_globals)]
-            #[::tiger_parse::distributed_slice(crate::STRUCTS)]
-            static #reflected_struct_ident: ::tiger_parse::reflect::ReflectedStruct = ::tiger_parse::reflect::ReflectedStruct {
-                id: #struct_id_or_zero,
-                name: std::borrow::Cow::Borrowed(stringify!(#ident)),
-                is_tuple: #is_tuple,
-                fields: std::borrow::Cow::Borrowed(&[
-                    #struct_reflect_field_stream
-                ]),
-                size: <#ident as ::tiger_parse::TigerReadable>::SIZE,
-            };
+            impl #impl_generics ::tiger_parse::reflect::ReflectType for #ident #ty_generics #where_clause {
+                const REFLECTED_TYPE: ::tiger_parse::reflect::ReflectedType =
+                    ::tiger_parse::reflect::ReflectedType::Other(std::borrow::Cow::Borrowed(stringify!(#ident)));
+            }
         });
+
+        if is_generic {
+            // Fields of type parameters are reflected through their `ReflectType` implementation
+            let mut reflect_generics = generics.clone();
+            for type_param in &type_params {
+                reflect_generics
+                    .make_where_clause()
+                    .predicates
+                    .push(syn::parse_quote!(#type_param: ::tiger_parse::reflect::ReflectType));
+            }
+            let (impl_generics, _, where_clause) = reflect_generics.split_for_impl();
+
+            // Generic structs can't be registered until they are instantiated, see `tiger_parse::reflect_instance!`
+            reflected_struct_stream.extend(quote! {
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    #[doc(hidden)]
+                    const __TIGER_REFLECT_FIELDS: &'static [::tiger_parse::reflect::ReflectedField] = &[
+                        #struct_reflect_field_stream
+                    ];
+
+                    #[doc(hidden)]
+                    pub const fn __tiger_reflect(name: &'static str) -> ::tiger_parse::reflect::ReflectedStruct {
+                        ::tiger_parse::reflect::ReflectedStruct {
+                            id: #struct_id_or_zero,
+                            name: std::borrow::Cow::Borrowed(name),
+                            is_tuple: #is_tuple,
+                            fields: std::borrow::Cow::Borrowed(Self::__TIGER_REFLECT_FIELDS),
+                            size: <Self as ::tiger_parse::TigerReadable>::SIZE,
+                        }
+                    }
+                }
+            });
+        } else {
+            let reflected_struct_ident = format_ident!("_{}_REFLECT", ident);
+            reflected_struct_stream.extend(quote! {
+                #[allow(non_upper_case_globals)]
+                #[::tiger_parse::distributed_slice(crate::STRUCTS)]
+                static #reflected_struct_ident: ::tiger_parse::reflect::ReflectedStruct = ::tiger_parse::reflect::ReflectedStruct {
+                    id: #struct_id_or_zero,
+                    name: std::borrow::Cow::Borrowed(stringify!(#ident)),
+                    is_tuple: #is_tuple,
+                    fields: std::borrow::Cow::Borrowed(&[
+                        #struct_reflect_field_stream
+                    ]),
+                    size: <#ident as ::tiger_parse::TigerReadable>::SIZE,
+                };
+            });
+        }
     }
 
     // Strip the tiger attribute from all fields
@@ -249,21 +598,132 @@ pub fn generate(
         f.attrs.retain(|v| !v.meta.path().is_ident("tiger"));
     }
 
+    let seek_to_end = quote! {
+        if <Self as ::tiger_parse::TigerReadable>::SIZE != (#fieldstream_size) {
+            reader.seek(::std::io::SeekFrom::Start(start_pos + <Self as ::tiger_parse::TigerReadable>::SIZE as u64))?;
+        }
+    };
+
+    let tracing_span = if cfg!(feature = "tracing") {
+        quote! {
+            let _span = ::tiger_parse::tracing::trace_span!(
+                "tiger_type",
+                r#type = %::tiger_parse::ShortName::of::<Self>(),
+                offset = start_pos,
+            )
+            .entered();
+        }
+    } else {
+        quote! {}
+    };
+
+    // If a custom size is specific, it must be at least the total sum of the field type sizes
+    // Generic structs can only be checked once they are instantiated, so the check is done when reading
+    let size_assert_message = "Declared struct size must be greater than or equal to the total sum of the field type sizes";
+    let (size_assert, generic_size_assert) = if is_generic {
+        (
+            quote! {},
+            quote! {
+                const {
+                    assert!(<Self as ::tiger_parse::TigerReadable>::SIZE >= (#fieldstream_size), #size_assert_message);
+                }
+            },
+        )
+    } else {
+        (
+            quote! {
+                const _: () = {
+                    assert!(<#ident as ::tiger_parse::TigerReadable>::SIZE >= (#fieldstream_size), #size_assert_message);
+                };
+            },
+            quote! {},
+        )
+    };
+
+    let data_tag_impl = if let Some(data_tag_field) = data_tag_field {
+        quote! {
+            impl #impl_generics ::tiger_parse::DataTagHeader for #ident #ty_generics #where_clause {
+                fn data_tag(&self) -> Option<::tiger_parse::tiger_pkg::TagHash> {
+                    ::tiger_parse::dpkg::valid_data_tag(self.#data_tag_field)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let visit_impl = if cfg!(feature = "visit") && opts.visit {
+        let mut generics = struc.generics.clone();
+        let type_params: Vec<Ident> = generics.type_params().map(|p| p.ident.clone()).collect();
+        for type_param in type_params {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote!(#type_param: ::tiger_parse::visit::TigerVisit));
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let visit_fields = if visit_stream.is_empty() {
+            quote! {}
+        } else {
+            quote! {
+                fn visit_fields(&self, path: &mut ::tiger_parse::visit::FieldPath, visitor: &mut dyn ::tiger_parse::visit::Visitor) {
+                    #visit_stream
+                }
+
+                fn visit_fields_mut(&mut self, path: &mut ::tiger_parse::visit::FieldPath, visitor: &mut dyn ::tiger_parse::visit::VisitorMut) {
+                    #visit_mut_stream
+                }
+            }
+        };
+
+        quote! {
+            impl #impl_generics ::tiger_parse::visit::TigerVisit for #ident #ty_generics #where_clause {
+                #visit_fields
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let diff_impl = if cfg!(feature = "diff") && opts.diff {
+        let mut generics = struc.generics.clone();
+        let type_params: Vec<Ident> = generics.type_params().map(|p| p.ident.clone()).collect();
+        for type_param in type_params {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote!(#type_param: ::tiger_parse::diff::TigerDiff));
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        quote! {
+            impl #impl_generics ::tiger_parse::diff::TigerDiff for #ident #ty_generics #where_clause {
+                #[allow(unused_variables)]
+                fn diff(&self, new: &Self, ctx: &mut ::tiger_parse::diff::DiffContext) {
+                    #diff_stream
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     let item_stream = struc.to_token_stream();
     let output = quote! {
         #[repr(C)]
         #item_stream
 
-        impl ::tiger_parse::TigerReadable for #ident {
+        impl #impl_generics ::tiger_parse::TigerReadable for #ident #ty_generics #where_clause {
             fn read_ds_endian(reader: &mut dyn ::tiger_parse::TigerReader, endian: ::tiger_parse::Endian) -> ::tiger_parse::Result<Self> {
                 use tiger_parse::ResultExt;
+                #generic_size_assert
                 let start_pos = reader.stream_position()?;
+                #tracing_span
 
                 #fieldstream
 
-                if <#ident as ::tiger_parse::TigerReadable>::SIZE != (#fieldstream_size) {
-                    reader.seek(::std::io::SeekFrom::Start(start_pos + <#ident as ::tiger_parse::TigerReadable>::SIZE as u64))?;
-                }
+                #seek_to_end
 
                 Ok(#return_statement)
             }
@@ -275,22 +735,110 @@ pub fn generate(
             #impl_struct_size
         }
 
+        #data_tag_impl
+
+        #visit_impl
+
+        #diff_impl
+
         #reflected_struct_stream
 
-        // If a custom size is specific, it must be at least the total sum of the field type sizes
-        const _: () = {
-            assert!(<#ident as ::tiger_parse::TigerReadable>::SIZE >= (#fieldstream_size), "Declared struct size must be greater than or equal to the total sum of the field type sizes");
-        };
+        #size_assert
     };
 
     output.into()
 }
 
-fn type_to_reflect(ty: &syn::Type) -> TokenStream {
+/// syn refuses to parse keywords as meta paths, so `#[tiger(if = "...")]` is rewritten to `#[tiger(condition = "...")]`
+fn rename_keyword_options(f: &mut syn::Field) {
+    for attr in f.attrs.iter_mut() {
+        let syn::Meta::List(list) = &mut attr.meta else {
+            continue;
+        };
+
+        if !list.path.is_ident("tiger") {
+            continue;
+        }
+
+        list.tokens = std::mem::take(&mut list.tokens)
+            .into_iter()
+            .map(|token| match token {
+                proc_macro2::TokenTree::Ident(ident) if ident == "if" => {
+                    proc_macro2::TokenTree::Ident(Ident::new("condition", ident.span()))
+                }
+                token => token,
+            })
+            .collect();
+    }
+}
+
+fn reflect_expression(expr: Option<&syn::Expr>) -> TokenStream {
+    if let Some(expr) = expr {
+        let expr = expr.to_token_stream().to_string();
+        quote!(Some(std::borrow::Cow::Borrowed(#expr)))
+    } else {
+        quote!(None)
+    }
+}
+
+/// Returns the size of a field in the struct layout.
+/// For `reserve`d conditional fields this is the size of the `T` in `Option<T>`, as absent fields are skipped over.
+/// Inline counted arrays and other conditional fields don't take up any fixed space, which makes the struct variable-size.
+fn field_size(ty: &syn::Type, d: &OptsField) -> Option<TokenStream> {
+    let value_type = field_value_type(ty, d)?;
+
+    Some(if let Some(size) = d.size {
+        quote!(#size)
+    } else if d.count.is_some() || (d.condition.is_some() && !d.reserve) {
+        quote!(0)
+    } else if let Some(map) = &d.map {
+        quote!(::tiger_parse::mapped_size(#map))
+    } else {
+        quote!(<#value_type as ::tiger_parse::TigerReadable>::SIZE)
+    })
+}
+
+/// Returns the type of the value that is read for a field, stripping the `Option` of conditional fields
+fn field_value_type<'a>(ty: &'a syn::Type, d: &OptsField) -> Option<&'a syn::Type> {
+    if d.condition.is_none() {
+        return Some(ty);
+    }
+
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+
+    let last_segment = type_path.path.segments.last()?;
+    if last_segment.ident != "Option" {
+        return None;
+    }
+
+    let syn::PathArguments::AngleBracketed(path_args) = &last_segment.arguments else {
+        return None;
+    };
+
+    match path_args.args.first()? {
+        syn::GenericArgument::Type(inner_ty) => Some(inner_ty),
+        _ => None,
+    }
+}
+
+/// Type parameters in `type_params` are resolved per instantiation through `ReflectType`
+fn type_to_reflect(ty: &syn::Type, type_params: &[Ident]) -> TokenStream {
+    if let syn::Type::Path(type_path) = ty {
+        if let Some(param) = type_path
+            .path
+            .get_ident()
+            .filter(|ident| type_params.contains(ident))
+        {
+            return quote!(<#param as ::tiger_parse::reflect::ReflectType>::REFLECTED_TYPE);
+        }
+    }
+
     let t = match ty {
         syn::Type::Array(type_array) => {
             let len = type_array.len.clone();
-            let element_type = type_to_reflect(&type_array.elem);
+            let element_type = type_to_reflect(&type_array.elem, type_params);
             quote! {
                 FixedArray(#len, ::tiger_parse::reflect::CowBox::Borrowed(&#element_type))
             }
@@ -312,6 +860,25 @@ fn type_to_reflect(ty: &syn::Type) -> TokenStream {
                 "Vec3" => quote!(Vec3),
                 "Vec4" => quote!(Vec4),
                 "TagHash" => quote!(TagHash),
+                "StringPointer" => quote!(StringPointer),
+                "LocalizedString" => quote!(LocalizedString),
+                "HashedName" => quote!(HashedName),
+                "Pointer" | "PointerOptional" => {
+                    let syn::PathArguments::AngleBracketed(path_args) =
+                        last_segment.arguments.clone()
+                    else {
+                        unreachable!("Expected angle bracketed arguments for pointer type");
+                    };
+
+                    let syn::GenericArgument::Type(inner_ty) = &path_args.args[0] else {
+                        unreachable!("Expected type argument for pointer type");
+                    };
+
+                    let inner_ty_reflected = type_to_reflect(inner_ty, type_params);
+                    let variant = &last_segment.ident;
+
+                    quote!(#variant(::tiger_parse::reflect::CowBox::Borrowed(&#inner_ty_reflected)))
+                }
                 "Vec" => {
                     let syn::PathArguments::AngleBracketed(path_args) =
                         last_segment.arguments.clone()
@@ -323,7 +890,7 @@ fn type_to_reflect(ty: &syn::Type) -> TokenStream {
                         unreachable!("Expected type argument for Vec type");
                     };
 
-                    let inner_ty_reflected = type_to_reflect(&inner_ty);
+                    let inner_ty_reflected = type_to_reflect(inner_ty, type_params);
 
                     quote!(Array(::tiger_parse::reflect::CowBox::Borrowed(&#inner_ty_reflected)))
                 }
@@ -333,7 +900,7 @@ fn type_to_reflect(ty: &syn::Type) -> TokenStream {
         syn::Type::Tuple(type_tuple) => {
             let mut fields = TokenStream::new();
             for field in &type_tuple.elems {
-                let field_reflected = type_to_reflect(field);
+                let field_reflected = type_to_reflect(field, type_params);
                 fields.extend(quote!(#field_reflected, ));
             }
             quote!(Tuple(std::borrow::Cow::Borrowed(&[#fields])))
@@ -0,0 +1,461 @@
+use darling::{ast::NestedMeta, FromMeta, FromVariant};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, FromMeta)]
+enum Dispatch {
+    /// Dispatch on the resource type preceding the pointer target
+    #[default]
+    #[darling(rename = "resource")]
+    Resource,
+
+    /// Dispatch on the class type of the class header
+    #[darling(rename = "class")]
+    Class,
+
+    /// Dispatch on both the resource type and the class type
+    #[darling(rename = "combined")]
+    Combined,
+}
+
+#[derive(FromMeta, Default, Debug)]
+#[darling(default)]
+struct Opts {
+    /// Offset from the resource pointer to the start of the variant data.
+    /// Defaults to the size of the class header when dispatching on the class type.
+    offset: Option<i64>,
+
+    /// Part of the resource header to dispatch on
+    dispatch: Dispatch,
+
+    /// Maximum number of bytes captured in the `data` field of the unknown variant
+    unknown_size: Option<usize>,
+
+    /// Implements `TigerVisit` with the `visit` feature, which requires it on every variant type
+    visit: bool,
+
+    /// Implements `TigerDiff` with the `diff` feature, which requires it on every variant type
+    diff: bool,
+}
+
+/// Default for `unknown_size`
+const DEFAULT_UNKNOWN_SIZE: usize = 0x1000;
+
+/// Size of the class header (`parent_tag` and `class_type`) following the resource type
+const CLASS_HEADER_SIZE: i64 = 8;
+
+#[derive(FromVariant, Default, Debug)]
+#[darling(default, attributes(tiger))]
+struct OptsVariant {
+    /// Explicit resource type, instead of the `ID` of the variant type
+    id: Option<u32>,
+
+    /// Class type to dispatch on with `dispatch = "class"` (instead of the `ID` of the variant type) or `dispatch = "combined"`
+    class: Option<u32>,
+
+    /// Class name whose FNV-1a hash is used as the class type, as an alternative to `class`
+    class_name: Option<String>,
+
+    /// Catch-all variant for unknown classes, with `class` and `offset` fields.
+    /// Optionally captures the raw bytes of the resource in a `data: Vec<u8>` field,
+    /// and the tag the resource belongs to in a `parent_tag` field.
+    /// With combined dispatch, `class` holds the resource type and an optional `class_type` field holds the class type.
+    unknown: bool,
+}
+
+pub fn generate(
+    attr: proc_macro::TokenStream,
+    mut enumm: syn::ItemEnum,
+) -> proc_macro::TokenStream {
+    let args = NestedMeta::parse_meta_list(attr.into()).unwrap();
+    let opts = match <Opts as darling::FromMeta>::from_list(&args) {
+        Ok(x) => x,
+        Err(e) => return e.write_errors().into(),
+    };
+
+    let ident = enumm.ident.clone();
+
+    let mut variant_idents: Vec<Ident> = vec![];
+    let mut variant_classes: Vec<TokenStream> = vec![];
+    let mut variant_class_idents: Vec<Ident> = vec![];
+    let mut variant_class_types: Vec<syn::Type> = vec![];
+    // Class types for combined dispatch
+    let mut variant_secondary_classes: Vec<TokenStream> = vec![];
+    let mut variant_secondary_idents: Vec<Ident> = vec![];
+    let mut unknown_ident: Option<Ident> = None;
+    let mut unknown_fields: Vec<String> = vec![];
+    for v in enumm.variants.iter() {
+        let d = match OptsVariant::from_variant(v) {
+            Ok(x) => x,
+            Err(e) => return e.write_errors().into(),
+        };
+
+        if d.unknown {
+            if unknown_ident.is_some() {
+                return quote! {
+                    compile_error!("Only one variant can be marked as #[tiger(unknown)]");
+                }
+                .into();
+            }
+
+            let syn::Fields::Named(fields) = &v.fields else {
+                return quote! {
+                    compile_error!("The #[tiger(unknown)] variant must have named `class` and `offset` fields");
+                }
+                .into();
+            };
+
+            unknown_ident = Some(v.ident.clone());
+            unknown_fields = fields
+                .named
+                .iter()
+                .filter_map(|f| f.ident.as_ref().map(|i| i.to_string()))
+                .collect();
+            continue;
+        }
+
+        let syn::Fields::Unnamed(fields) = &v.fields else {
+            return quote! {
+                compile_error!("Variants must have a single unnamed field holding the variant data");
+            }
+            .into();
+        };
+
+        if fields.unnamed.len() != 1 {
+            return quote! {
+                compile_error!("Variants must have a single unnamed field holding the variant data");
+            }
+            .into();
+        }
+
+        let explicit_class = match (d.class, &d.class_name) {
+            (Some(_), Some(_)) => {
+                return quote! {
+                    compile_error!("#[tiger(class = ..)] and #[tiger(class_name = ..)] are mutually exclusive");
+                }
+                .into();
+            }
+            (Some(class), None) => Some(quote!(#class)),
+            (None, Some(name)) => Some(quote!(::tiger_parse::fnv1a32(#name.as_bytes()))),
+            (None, None) => None,
+        };
+
+        let class_type = unbox_type(&fields.unnamed[0].ty).clone();
+        let type_id =
+            quote!(<#class_type as ::tiger_parse::TigerReadable>::ID.expect("Missing class ID"));
+        let class = match opts.dispatch {
+            Dispatch::Resource | Dispatch::Combined => match d.id {
+                Some(id) => quote!(#id),
+                None => type_id,
+            },
+            Dispatch::Class => {
+                if d.id.is_some() {
+                    return quote! {
+                        compile_error!("Variants of enums with class dispatch use #[tiger(class = ..)] instead of #[tiger(id = ..)]");
+                    }
+                    .into();
+                }
+
+                match explicit_class.clone() {
+                    Some(class) => class,
+                    None => type_id,
+                }
+            }
+        };
+
+        if opts.dispatch == Dispatch::Combined {
+            let Some(class) = explicit_class else {
+                return quote! {
+                    compile_error!("Variants of enums with combined dispatch require #[tiger(class = ..)] or #[tiger(class_name = ..)]");
+                }
+                .into();
+            };
+
+            variant_secondary_classes.push(class);
+            variant_secondary_idents.push(format_ident!("{}_CLASS_TYPE", v.ident));
+        } else if explicit_class.is_some() && opts.dispatch == Dispatch::Resource {
+            return quote! {
+                compile_error!("#[tiger(class = ..)] and #[tiger(class_name = ..)] require dispatch = \"class\" or dispatch = \"combined\"");
+            }
+            .into();
+        }
+
+        variant_class_idents.push(format_ident!("{}_CLASS", v.ident));
+        variant_idents.push(v.ident.clone());
+        variant_classes.push(class);
+        variant_class_types.push(class_type);
+    }
+
+    let extra_offset = opts
+        .offset
+        .or((opts.dispatch != Dispatch::Resource).then_some(CLASS_HEADER_SIZE))
+        .map(|offset| {
+            quote! {
+                const EXTRA_OFFSET: i64 = #offset;
+            }
+        });
+
+    let dispatch = match opts.dispatch {
+        Dispatch::Resource => quote!(ResourceType),
+        Dispatch::Class => quote!(ClassType),
+        Dispatch::Combined => quote!(Combined),
+    };
+
+    let (unknown_class_id, unknown_class_name, unknown_body) = if let Some(unknown_ident) =
+        &unknown_ident
+    {
+        let mut unknown_reads = TokenStream::new();
+        let mut unknown_assign = TokenStream::new();
+        if unknown_fields.iter().any(|f| f == "parent_tag") {
+            // The parent tag directly follows the resource type, at the start of the resource
+            unknown_reads.extend(quote! {
+                reader.seek(::std::io::SeekFrom::Start(
+                    offset.saturating_add_signed(-<Self as ::tiger_parse::VariantEnum>::EXTRA_OFFSET),
+                ))?;
+                let parent_tag = ::tiger_parse::TigerReadable::read_ds_endian(reader, endian)?;
+                reader.seek(::std::io::SeekFrom::Start(offset))?;
+            });
+            unknown_assign.extend(quote!(parent_tag,));
+        }
+
+        if unknown_fields.iter().any(|f| f == "class_type") {
+            if opts.dispatch != Dispatch::Combined {
+                return quote! {
+                    compile_error!("The `class_type` field of the unknown variant is only available with combined dispatch");
+                }
+                .into();
+            }
+
+            unknown_assign.extend(quote!(class_type,));
+        }
+
+        if unknown_fields.iter().any(|f| f == "data") {
+            let unknown_size = opts.unknown_size.unwrap_or(DEFAULT_UNKNOWN_SIZE);
+            unknown_reads.extend(quote! {
+                let data = ::tiger_parse::variant::read_unknown_variant_data(
+                    reader,
+                    endian,
+                    #unknown_size,
+                    &[#(#variant_class_idents),*],
+                )?;
+            });
+            unknown_assign.extend(quote!(data,));
+        }
+
+        (
+            quote!(Self::#unknown_ident { class, .. } => class,),
+            quote!(Self::#unknown_ident { .. } => "Unknown",),
+            quote! {
+                {
+                    let offset = reader.stream_position()?;
+                    #unknown_reads
+                    Ok(Self::#unknown_ident {
+                        class,
+                        offset,
+                        #unknown_assign
+                    })
+                }
+            },
+        )
+    } else {
+        (
+            quote!(),
+            quote!(),
+            quote! {
+                Err(::tiger_parse::Error::MissingVariantType {
+                    class,
+                    typename: ::tiger_parse::ShortName::of::<Self>().to_string(),
+                })
+            },
+        )
+    };
+
+    let read_variant = if opts.dispatch == Dispatch::Combined {
+        quote! {
+            fn read_variant_endian(
+                _reader: &mut dyn ::tiger_parse::TigerReader,
+                _endian: ::tiger_parse::Endian,
+                _class: u32,
+            ) -> ::tiger_parse::Result<Self> {
+                Err(::tiger_parse::Error::InvalidStructure(format!(
+                    "Variant enum {} dispatches on the full resource header",
+                    ::tiger_parse::ShortName::of::<Self>()
+                )))
+            }
+
+            fn read_variant_header(
+                reader: &mut dyn ::tiger_parse::TigerReader,
+                endian: ::tiger_parse::Endian,
+                header: &::tiger_parse::VariantHeader,
+            ) -> ::tiger_parse::Result<Self> {
+                use ::tiger_parse::ResultExt;
+                #(const #variant_class_idents: u32 = #variant_classes;)*
+                #(const #variant_secondary_idents: u32 = #variant_secondary_classes;)*
+                match (header.resource_type, header.class_type) {
+                    #(
+                        (#variant_class_idents, Some(#variant_secondary_idents)) => Ok(Self::#variant_idents(
+                            ::tiger_parse::TigerReadable::read_ds_endian(reader, endian)
+                                .with_field(&::tiger_parse::ShortName::of::<Self>().to_string(), stringify!(#variant_idents))?,
+                        )),
+                    )*
+                    (class, class_type) => {
+                        #[allow(unused_variables)]
+                        let class_type = class_type.unwrap_or(u32::MAX);
+                        #unknown_body
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {
+            fn read_variant_endian(
+                reader: &mut dyn ::tiger_parse::TigerReader,
+                endian: ::tiger_parse::Endian,
+                class: u32,
+            ) -> ::tiger_parse::Result<Self> {
+                use ::tiger_parse::ResultExt;
+                #(const #variant_class_idents: u32 = #variant_classes;)*
+                match class {
+                    #(
+                        #variant_class_idents => Ok(Self::#variant_idents(
+                            ::tiger_parse::TigerReadable::read_ds_endian(reader, endian)
+                                .with_field(&::tiger_parse::ShortName::of::<Self>().to_string(), stringify!(#variant_idents))?,
+                        )),
+                    )*
+                    class => #unknown_body
+                }
+            }
+        }
+    };
+
+    let visit_impl = if cfg!(feature = "visit") && opts.visit {
+        quote! {
+            impl ::tiger_parse::visit::TigerVisit for #ident {
+                fn visit_fields(&self, path: &mut ::tiger_parse::visit::FieldPath, visitor: &mut dyn ::tiger_parse::visit::Visitor) {
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        #(
+                            Self::#variant_idents(v) => ::tiger_parse::visit::visit_child(
+                                v,
+                                ::tiger_parse::visit::PathSegment::Variant(stringify!(#variant_idents)),
+                                path,
+                                visitor,
+                            ),
+                        )*
+                        _ => {}
+                    }
+                }
+
+                fn visit_fields_mut(&mut self, path: &mut ::tiger_parse::visit::FieldPath, visitor: &mut dyn ::tiger_parse::visit::VisitorMut) {
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        #(
+                            Self::#variant_idents(v) => ::tiger_parse::visit::visit_child_mut(
+                                v,
+                                ::tiger_parse::visit::PathSegment::Variant(stringify!(#variant_idents)),
+                                path,
+                                visitor,
+                            ),
+                        )*
+                        _ => {}
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let diff_impl = if cfg!(feature = "diff") && opts.diff {
+        let unknown_variant_name = unknown_ident.as_ref().map(
+            |unknown_ident| quote!(Self::#unknown_ident { .. } => stringify!(#unknown_ident),),
+        );
+
+        quote! {
+            impl ::tiger_parse::diff::TigerDiff for #ident {
+                fn diff(&self, new: &Self, ctx: &mut ::tiger_parse::diff::DiffContext) {
+                    #[allow(unreachable_patterns)]
+                    match (self, new) {
+                        #(
+                            (Self::#variant_idents(old), Self::#variant_idents(new)) => ctx.child(
+                                ::tiger_parse::visit::PathSegment::Variant(stringify!(#variant_idents)),
+                                old,
+                                new,
+                            ),
+                        )*
+                        _ => {
+                            let name = |v: &Self| match v {
+                                #(Self::#variant_idents(_) => stringify!(#variant_idents),)*
+                                #unknown_variant_name
+                            };
+                            ::tiger_parse::diff::diff_variant(
+                                (name(self), self.class_id()),
+                                (name(new), new.class_id()),
+                                ctx,
+                            )
+                        }
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Strip the tiger attribute from all variants
+    for v in enumm.variants.iter_mut() {
+        v.attrs.retain(|v| !v.meta.path().is_ident("tiger"));
+    }
+
+    quote! {
+        #enumm
+
+        #visit_impl
+
+        #diff_impl
+
+        impl #ident {
+            /// Returns the key this variant is dispatched on (the resource type for combined dispatch)
+            pub fn class_id(&self) -> u32 {
+                match *self {
+                    #(Self::#variant_idents(_) => #variant_classes,)*
+                    #unknown_class_id
+                }
+            }
+
+            pub fn class_name(&self) -> &'static str {
+                match *self {
+                    #(Self::#variant_idents(_) => ::tiger_parse::ShortName::of::<#variant_class_types>().0,)*
+                    #unknown_class_name
+                }
+            }
+        }
+
+        #[allow(non_upper_case_globals)]
+        impl ::tiger_parse::VariantEnum for #ident {
+            #extra_offset
+
+            const DISPATCH: ::tiger_parse::VariantDispatch = ::tiger_parse::VariantDispatch::#dispatch;
+
+            #read_variant
+        }
+    }
+    .into()
+}
+
+/// Returns the `T` in `Box<T>`, or the type itself
+fn unbox_type(ty: &syn::Type) -> &syn::Type {
+    if let syn::Type::Path(type_path) = ty {
+        if let Some(last_segment) = type_path.path.segments.last() {
+            if last_segment.ident == "Box" {
+                if let syn::PathArguments::AngleBracketed(path_args) = &last_segment.arguments {
+                    if let Some(syn::GenericArgument::Type(inner_ty)) = path_args.args.first() {
+                        return inner_ty;
+                    }
+                }
+            }
+        }
+    }
+
+    ty
+}